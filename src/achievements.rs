@@ -0,0 +1,336 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{localization::{LanguageChanged, Localization}, profile::{save_profile, Profile, ShiftStatistics, Statistics}, GameState};
+
+/// How long an unlock toast stays on screen.
+const TOAST_DURATION: f32 = 4.0;
+/// How long a toast takes to fade out at the end of its life.
+const TOAST_FADE_DURATION: f32 = 1.0;
+const TOAST_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.85);
+const TEXT_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+const LOCKED_TEXT_COLOR: Color = Color::rgb(0.45, 0.45, 0.45);
+
+pub struct AchievementsPlugin;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Achievement {
+    FirstHarvest,
+    PoserWrangler,
+    PsychoWhisperer,
+    AttentiveGardener,
+    Marathon,
+    DaycareVeteran,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 6] = [
+        Achievement::FirstHarvest,
+        Achievement::PoserWrangler,
+        Achievement::PsychoWhisperer,
+        Achievement::AttentiveGardener,
+        Achievement::Marathon,
+        Achievement::DaycareVeteran,
+    ];
+
+    /// Key of the title in the string tables.
+    pub fn title_key(&self) -> &'static str {
+        match self {
+            Achievement::FirstHarvest => "achievements.first_harvest",
+            Achievement::PoserWrangler => "achievements.poser_wrangler",
+            Achievement::PsychoWhisperer => "achievements.psycho_whisperer",
+            Achievement::AttentiveGardener => "achievements.attentive_gardener",
+            Achievement::Marathon => "achievements.marathon",
+            Achievement::DaycareVeteran => "achievements.daycare_veteran",
+        }
+    }
+
+    pub fn description_key(&self) -> &'static str {
+        match self {
+            Achievement::FirstHarvest => "achievements.first_harvest.description",
+            Achievement::PoserWrangler => "achievements.poser_wrangler.description",
+            Achievement::PsychoWhisperer => "achievements.psycho_whisperer.description",
+            Achievement::AttentiveGardener => "achievements.attentive_gardener.description",
+            Achievement::Marathon => "achievements.marathon.description",
+            Achievement::DaycareVeteran => "achievements.daycare_veteran.description",
+        }
+    }
+
+    fn is_achieved(&self, statistics: &Statistics, shift: &ShiftStatistics) -> bool {
+        match self {
+            Achievement::FirstHarvest => statistics.fungi_raised >= 1,
+            Achievement::PoserWrangler => shift.poser_streak >= 5,
+            Achievement::PsychoWhisperer => shift.psychos_delivered >= 3,
+            Achievement::AttentiveGardener => shift.time_without_bubble >= 3.0 * 60.0,
+            Achievement::Marathon => shift.survival_time >= 10.0 * 60.0,
+            Achievement::DaycareVeteran => statistics.fungi_raised >= 100,
+        }
+    }
+}
+
+#[derive(Event, Debug)]
+pub struct AchievementUnlockedEvent(pub Achievement);
+
+/// Button which shows or hides the achievement list.
+#[derive(Component, Debug)]
+pub struct ToggleAchievementList;
+
+#[derive(Component, Debug)]
+struct AchievementList;
+
+#[derive(Component, Debug)]
+struct ToastContainer;
+
+#[derive(Component, Debug)]
+struct AchievementToast {
+    timer: Timer,
+}
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<AchievementUnlockedEvent>()
+            .add_systems(Startup, spawn_toast_container)
+            .add_systems(Update, (
+                unlock_achievements,
+            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))))
+            .add_systems(Update, (spawn_toasts, update_toasts))
+            .add_systems(Update, toggle_achievement_list.run_if(in_state(GameState::Menu).or_else(in_state(GameState::GameOver))))
+            .add_systems(Update, respawn_achievement_list.run_if(on_event::<LanguageChanged>()))
+            .add_systems(OnExit(GameState::Menu), cleanup_achievement_list)
+            .add_systems(OnExit(GameState::GameOver), cleanup_achievement_list);
+    }
+}
+
+fn unlock_achievements(
+    shift: Res<ShiftStatistics>,
+    mut profile: ResMut<Profile>,
+    mut unlocked_events: EventWriter<AchievementUnlockedEvent>,
+) {
+    let newly_unlocked = Achievement::ALL.iter()
+        .filter(|achievement| !profile.achievements.contains(achievement))
+        .filter(|achievement| achievement.is_achieved(&profile.statistics, &shift))
+        .copied()
+        .collect::<Vec<_>>();
+
+    if newly_unlocked.is_empty() { return; }
+
+    for achievement in newly_unlocked {
+        profile.achievements.push(achievement);
+        unlocked_events.send(AchievementUnlockedEvent(achievement));
+    }
+
+    save_profile(&profile);
+}
+
+fn spawn_toast_container(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(80.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        ToastContainer,
+    ));
+}
+
+fn spawn_toasts(
+    mut commands: Commands,
+    mut events: EventReader<AchievementUnlockedEvent>,
+    container: Query<Entity, With<ToastContainer>>,
+    localization: Res<Localization>,
+) {
+    let Ok(container) = container.get_single() else { return };
+
+    for AchievementUnlockedEvent(achievement) in events.read() {
+        let toast = commands.spawn((
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(15.0)),
+                    ..default()
+                },
+                background_color: TOAST_COLOR.into(),
+                ..default()
+            },
+            AchievementToast {
+                timer: Timer::from_seconds(TOAST_DURATION, TimerMode::Once),
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                localization.text("achievements.unlocked"),
+                TextStyle {
+                    font_size: 20.0,
+                    color: TEXT_COLOR,
+                    ..default()
+                },
+            ));
+            parent.spawn(TextBundle::from_section(
+                localization.text(achievement.title_key()),
+                TextStyle {
+                    font_size: 36.0,
+                    color: TEXT_COLOR,
+                    ..default()
+                },
+            ));
+        }).id();
+
+        commands.entity(container).add_child(toast);
+    }
+}
+
+fn update_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toasts: Query<(Entity, &mut AchievementToast, &mut BackgroundColor, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, mut toast, mut background, children) in &mut toasts {
+        toast.timer.tick(time.delta());
+
+        if toast.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let alpha = (toast.timer.remaining_secs() / TOAST_FADE_DURATION).min(1.0);
+        background.0.set_a(TOAST_COLOR.a() * alpha);
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                for section in text.sections.iter_mut() {
+                    section.style.color.set_a(alpha);
+                }
+            }
+        }
+    }
+}
+
+fn toggle_achievement_list(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ToggleAchievementList>)>,
+    list_query: Query<Entity, With<AchievementList>>,
+    profile: Res<Profile>,
+    localization: Res<Localization>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed { continue; }
+
+        if list_query.is_empty() {
+            spawn_achievement_list(&mut commands, &profile, &localization);
+        } else {
+            for entity in &list_query {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// An open list stays open when the language changes, but in the new language.
+fn respawn_achievement_list(
+    mut commands: Commands,
+    list_query: Query<Entity, With<AchievementList>>,
+    profile: Res<Profile>,
+    localization: Res<Localization>,
+) {
+    if list_query.is_empty() { return; }
+
+    for entity in &list_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_achievement_list(&mut commands, &profile, &localization);
+}
+
+fn spawn_achievement_list(commands: &mut Commands, profile: &Profile, localization: &Localization) {
+    let statistics = &profile.statistics;
+    let best_time = statistics.best_survival_time as u32;
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(25.0),
+                top: Val::Px(25.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(20.0)),
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            background_color: TOAST_COLOR.into(),
+            z_index: ZIndex::Global(5),
+            ..default()
+        },
+        AchievementList,
+    ))
+    .with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            localization.format("achievements.list", &[
+                ("unlocked", &profile.achievements.len()),
+                ("total", &Achievement::ALL.len()),
+            ]),
+            TextStyle {
+                font_size: 36.0,
+                color: TEXT_COLOR,
+                ..default()
+            },
+        ));
+
+        for achievement in Achievement::ALL {
+            let color = if profile.achievements.contains(&achievement) {
+                TEXT_COLOR
+            } else {
+                LOCKED_TEXT_COLOR
+            };
+
+            parent.spawn(TextBundle::from_sections([
+                TextSection::new(
+                    format!("{}\n", localization.text(achievement.title_key())),
+                    TextStyle {
+                        font_size: 24.0,
+                        color,
+                        ..default()
+                    },
+                ),
+                TextSection::new(
+                    localization.text(achievement.description_key()),
+                    TextStyle {
+                        font_size: 16.0,
+                        color,
+                        ..default()
+                    },
+                ),
+            ]));
+        }
+
+        parent.spawn(TextBundle::from_section(
+            localization.format("achievements.statistics", &[
+                ("fungi", &statistics.fungi_raised),
+                ("derps", &statistics.derps_raised),
+                ("psychos", &statistics.psychos_raised),
+                ("posers", &statistics.posers_raised),
+                ("best_shift", &format!("{}:{:02}", best_time / 60, best_time % 60)),
+                ("hypnosis", &statistics.hypnosis_incidents),
+            ]),
+            TextStyle {
+                font_size: 16.0,
+                color: TEXT_COLOR,
+                ..default()
+            },
+        ));
+    });
+}
+
+fn cleanup_achievement_list(mut commands: Commands, list_query: Query<Entity, With<AchievementList>>) {
+    for entity in &list_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,17 +1,179 @@
 use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::parents::Species;
+
+/// Large enough for six frames of the biggest animation.
+const ATLAS_MAX_SIZE: Vec2 = Vec2::splat(4096.0);
+/// Keeps neighbouring frames from bleeding into each other when filtered.
+const ATLAS_PADDING: UVec2 = UVec2::splat(2);
 
 pub struct AnimationsPlugin;
 
 impl Plugin for AnimationsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, update_animations);
+        app
+            .add_event::<AnimationFrameEvent>()
+            .add_event::<AnimationFinished>()
+            .add_systems(Update, update_animations);
     }
 }
 
+/// What a mushroom is doing in an animation.
+#[derive(Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum AnimationState {
+    Walking,
+    Patient,
+    Nervous,
+    /// Grown up psycho and the swirl it hypnotizes with.
+    Hypnotic,
+    /// Growth stages of a child, in the order it goes through them.
+    Spores,
+    Baby,
+    Child,
+    Teenager,
+    Grown,
+}
+
+/// Layer of a mushroom drawn by an animation.
+#[derive(Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum AnimationPart {
+    Body,
+    Eyes,
+    /// Drawn around the mushroom, not on it.
+    Effect,
+}
+
+/// Identifies an animation in the animation manifest.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct AnimationKey {
+    pub species: Species,
+    pub state: AnimationState,
+    pub part: AnimationPart,
+}
+
+/// Something happening on a frame of an animation, for other systems to sync to.
+#[derive(Deserialize, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum AnimationMarker {
+    Footstep,
+    Blink,
+}
+
+/// Sent when an animation shows a frame with a marker.
+#[derive(Event, Debug)]
+pub struct AnimationFrameEvent {
+    pub entity: Entity,
+    pub marker: AnimationMarker,
+}
+
+/// Sent when a one-shot animation played its last frame.
+#[derive(Event, Debug)]
+pub struct AnimationFinished {
+    pub entity: Entity,
+}
+
+
+/// Frames of an animation, packed into a single texture atlas.
+#[derive(Clone, Default, Debug)]
+pub struct AnimationFrames {
+    pub texture: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    /// Atlas index of each frame, in the order they are played.
+    pub indices: Vec<usize>,
+    /// Markers sent when the frame with the given index is shown.
+    pub markers: Vec<(usize, AnimationMarker)>,
+}
+
+impl AnimationFrames {
+    /// Packs the loaded frames into a new atlas. Frames can repeat, they are packed only once.
+    pub fn pack(
+        frames: &[Handle<Image>],
+        images: &mut Assets<Image>,
+        layouts: &mut Assets<TextureAtlasLayout>,
+    ) -> Self {
+        let mut builder = TextureAtlasBuilder::default()
+            .max_size(ATLAS_MAX_SIZE)
+            .padding(ATLAS_PADDING);
+
+        let mut added = Vec::new();
+        for frame in frames {
+            if added.contains(&frame.id()) { continue; }
+
+            let Some(image) = images.get(frame) else {
+                warn!("animation frame {:?} is not loaded", frame.path());
+                continue;
+            };
+            builder.add_texture(Some(frame.id()), image);
+            added.push(frame.id());
+        }
+
+        let (layout, atlas) = match builder.finish() {
+            Ok(packed) => packed,
+            Err(err) => {
+                error!("failed to pack animation frames: {}", err);
+                return AnimationFrames::single(frames.first().cloned().unwrap_or_default());
+            }
+        };
+
+        let indices = frames.iter()
+            .filter_map(|frame| layout.get_texture_index(frame))
+            .collect();
+
+        AnimationFrames {
+            texture: images.add(atlas),
+            layout: layouts.add(layout),
+            indices,
+            markers: Vec::new(),
+        }
+    }
+
+    /// Still image, drawn whole as there is no atlas layout.
+    pub fn single(texture: Handle<Image>) -> Self {
+        AnimationFrames {
+            texture,
+            layout: Handle::default(),
+            indices: vec![0],
+            markers: Vec::new(),
+        }
+    }
+
+    /// Markers past the last frame would never be sent, so they are dropped with a warning.
+    pub fn with_marker(mut self, frame: usize, marker: AnimationMarker) -> Self {
+        if frame >= self.indices.len() {
+            warn!("marker {:?} is on frame {}, but the animation has only {} frames", marker, frame, self.indices.len());
+            return self;
+        }
+
+        self.markers.push((frame, marker));
+        self
+    }
+
+    /// Same frames played backwards, markers stay on their frames.
+    pub fn reversed(&self) -> Self {
+        let last = self.indices.len().saturating_sub(1);
+        AnimationFrames {
+            texture: self.texture.clone(),
+            layout: self.layout.clone(),
+            indices: self.indices.iter().rev().copied().collect(),
+            markers: self.markers.iter().filter_map(|&(frame, marker)| Some((last.checked_sub(frame)?, marker))).collect(),
+        }
+    }
+}
+
+/// What happens after the last frame.
+#[derive(Clone, Debug)]
+pub enum Playback {
+    Loop,
+    /// Plays the frames once, then switches to the follow-up animation, or stays on the last frame.
+    Once(Option<AnimationFrames>),
+    /// One-shot animation which already finished.
+    Stopped,
+}
 
 #[derive(Component)]
 pub struct Animation {
-    pub frames: Vec<Handle<Image>>,
+    pub frames: AnimationFrames,
+    pub playback: Playback,
     pub frame_index: usize,
     pub frame_period: f32,
     pub timer: f32,
@@ -19,9 +181,10 @@ pub struct Animation {
 }
 
 impl Animation {
-    pub fn new(frames: Vec<Handle<Image>>, frame_period: f32) -> Self {
+    pub fn new(frames: AnimationFrames, frame_period: f32) -> Self {
         Self {
             frames,
+            playback: Playback::Loop,
             frame_period,
             frame_index: 0_usize,
             timer: 0_f32,
@@ -29,31 +192,71 @@ impl Animation {
         }
     }
 
-    #[allow(dead_code)]
-    pub fn change_frames(&mut self, frames: Vec<Handle<Image>>) {
+    pub fn change_frames(&mut self, frames: AnimationFrames) {
         self.frames = frames;
+        self.playback = Playback::Loop;
         self.timer = 0.0;
         self.is_changed = true;
     }
+
+    /// Plays the frames once and then the follow-up animation in a loop, if there is one.
+    pub fn play_once(&mut self, frames: AnimationFrames, then: Option<AnimationFrames>) {
+        self.change_frames(frames);
+        self.playback = Playback::Once(then);
+    }
 }
 
 pub fn update_animations(
-    mut animation_query: Query<(&mut Animation, &mut Handle<Image>)>,
+    mut animation_query: Query<(Entity, &mut Animation, &mut Handle<Image>, &mut TextureAtlas)>,
+    mut frame_events: EventWriter<AnimationFrameEvent>,
+    mut finished_events: EventWriter<AnimationFinished>,
     time: Res<Time>
 ) {
-    for (mut animation, mut sprite) in animation_query.iter_mut() {
-        if animation.frames.len() == 0 { continue; }
+    for (entity, mut animation, mut texture, mut atlas) in animation_query.iter_mut() {
+        if animation.frames.indices.is_empty() { continue; }
 
-        animation.timer += time.delta_seconds();
+        if !animation.is_changed {
+            if matches!(animation.playback, Playback::Stopped) { continue; }
 
-        if animation.timer > animation.frame_period || animation.is_changed {
+            animation.timer += time.delta_seconds();
+            if animation.timer <= animation.frame_period { continue; }
             animation.timer %= animation.frame_period;
 
-            animation.frame_index = (animation.frame_index + 1) % animation.frames.len();
-            *sprite = animation.frames[animation.frame_index].clone();
+            let next = animation.frame_index + 1;
+            if next < animation.frames.indices.len() {
+                animation.frame_index = next;
+            } else {
+                match std::mem::replace(&mut animation.playback, Playback::Stopped) {
+                    Playback::Loop => {
+                        animation.playback = Playback::Loop;
+                        animation.frame_index = 0;
+                    },
+                    Playback::Once(then) => {
+                        finished_events.send(AnimationFinished { entity });
+                        let Some(then) = then else { continue };
+
+                        animation.change_frames(then);
+                    },
+                    Playback::Stopped => continue,
+                }
+            }
+        }
 
+        if animation.is_changed {
+            *texture = animation.frames.texture.clone();
+            atlas.layout = animation.frames.layout.clone();
+            animation.frame_index = 0;
             animation.is_changed = false;
         }
+
+        let frame_index = animation.frame_index;
+        atlas.index = animation.frames.indices[frame_index];
+
+        for &(frame, marker) in &animation.frames.markers {
+            if frame == frame_index {
+                frame_events.send(AnimationFrameEvent { entity, marker });
+            }
+        }
     }
 }
 
@@ -64,35 +267,41 @@ pub struct AnimationBundle {
 }
 
 impl AnimationBundle {
-    pub fn new(frames: Vec<Handle<Image>>, frame_period: f32, scale: f32, z: f32) -> Self {
+    pub fn new(frames: AnimationFrames, frame_period: f32, scale: f32, z: f32) -> Self {
         let t = Transform::from_translation(Vec3::new(0.0, 0.0, z)).with_scale(Vec3::new(scale, scale, scale));
-        let first = frames[0].clone();
 
         Self {
-            animation: Animation::new(frames, frame_period),
             sprite_sheet: SpriteSheetBundle {
                 transform: t,
-                texture: first,
+                texture: frames.texture.clone(),
+                atlas: TextureAtlas {
+                    layout: frames.layout.clone(),
+                    index: frames.indices.first().copied().unwrap_or_default(),
+                },
                 ..default()
-            }
+            },
+            animation: Animation::new(frames, frame_period),
         }
     }
 
-    pub fn new_with_size(frames: Vec<Handle<Image>>, frame_period: f32, size: f32, z: f32) -> Self {
+    pub fn new_with_size(frames: AnimationFrames, frame_period: f32, size: f32, z: f32) -> Self {
         let t = Transform::from_translation(Vec3::new(0.0, 0.0, z));
-        let first = frames[0].clone();
 
         Self {
-            animation: Animation::new(frames, frame_period),
             sprite_sheet: SpriteSheetBundle {
                 transform: t,
-                texture: first,
+                texture: frames.texture.clone(),
+                atlas: TextureAtlas {
+                    layout: frames.layout.clone(),
+                    index: frames.indices.first().copied().unwrap_or_default(),
+                },
                 sprite: Sprite {
                     custom_size: Some(Vec2::splat(size)),
                     ..default()
                 },
                 ..default()
-            }
+            },
+            animation: Animation::new(frames, frame_period),
         }
     }
-}
\ No newline at end of file
+}
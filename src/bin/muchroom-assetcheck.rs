@@ -0,0 +1,304 @@
+//! Checks the assets referenced by the game before they break the build.
+//!
+//! Reports missing files, frames of mismatched sizes, markers past the last frame,
+//! species with different frame counts and files nobody uses.
+//! Exits with a non-zero status if any file is missing or broken.
+//!
+//! Run from the repository root: `cargo run --bin muchroom-assetcheck`
+
+use std::{collections::{BTreeMap, BTreeSet}, fs, path::{Path, PathBuf}, process::ExitCode};
+
+use serde::{de::{self, DeserializeSeed, EnumAccess, VariantAccess, Visitor}, Deserialize, Deserializer};
+
+const ASSETS_DIR: &str = "assets";
+/// Asset collections with their `#[asset(path)]` attributes.
+const LOADING_SOURCE: &str = include_str!("../loading.rs");
+const ANIMATION_MANIFEST: &str = "animations.manifest.ron";
+/// Empty image standing in for the parts an animation doesn't have, like the eyes of spores.
+const BLANK_FRAME: &str = "textures/nothing.png";
+/// Data files read outside of the asset collections, the values are not checked, only that the files parse.
+/// RON files of the collections are checked the same way.
+const DATA_FILES: [&str; 2] = ["locale/en.ron", "locale/cs.ron"];
+/// Files embedded into the game, and the font license.
+const OTHER_FILES: [&str; 2] = ["fonts/DejaVuSansMono.ttf", "fonts/DejaVuSansMono-LICENSE.txt"];
+
+/// Name of an enum variant, read without knowing the enum, so that new species or states don't need a change here.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct VariantName(String);
+
+impl std::fmt::Display for VariantName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for VariantName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_enum("VariantName", &[], VariantNameVisitor)
+    }
+}
+
+struct VariantNameVisitor;
+
+impl<'de> Visitor<'de> for VariantNameVisitor {
+    type Value = VariantName;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a unit variant")
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<VariantName, E> {
+        Ok(VariantName(name.to_string()))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<VariantName, A::Error> {
+        let (name, variant) = data.variant_seed(VariantNameSeed)?;
+        variant.unit_variant()?;
+        Ok(name)
+    }
+}
+
+/// Reads the variant itself, which RON writes as a bare identifier.
+struct VariantNameSeed;
+
+impl<'de> DeserializeSeed<'de> for VariantNameSeed {
+    type Value = VariantName;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<VariantName, D::Error> {
+        deserializer.deserialize_identifier(VariantNameVisitor)
+    }
+}
+
+// mirror of the manifest types in `loading.rs`
+#[derive(Deserialize)]
+struct ManifestEntry {
+    species: VariantName,
+    state: VariantName,
+    part: VariantName,
+    frames: Vec<String>,
+    #[serde(default)]
+    markers: Vec<(usize, VariantName)>,
+}
+
+#[derive(Deserialize)]
+struct ManifestFile {
+    animations: Vec<ManifestEntry>,
+}
+
+#[derive(Default)]
+struct Report {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Report {
+    fn error(&mut self, message: String) {
+        self.errors.push(message);
+    }
+
+    fn warning(&mut self, message: String) {
+        self.warnings.push(message);
+    }
+}
+
+fn main() -> ExitCode {
+    let assets = PathBuf::from(ASSETS_DIR);
+    if !assets.is_dir() {
+        eprintln!("no `{}` directory, run the tool from the repository root", ASSETS_DIR);
+        return ExitCode::FAILURE;
+    }
+
+    let mut report = Report::default();
+    let mut referenced = BTreeSet::new();
+
+    for path in collection_paths(LOADING_SOURCE, &mut report) {
+        if !assets.join(&path).is_file() {
+            report.error(format!("missing file {} referenced in src/loading.rs", path));
+        } else if path.ends_with(".ron") {
+            check_data_file(&assets, &path, &mut report);
+        }
+        referenced.insert(path);
+    }
+
+    referenced.insert(ANIMATION_MANIFEST.to_string());
+    match fs::read_to_string(assets.join(ANIMATION_MANIFEST)) {
+        Ok(contents) => match ron::from_str::<ManifestFile>(&contents) {
+            Ok(manifest) => check_manifest(&assets, &manifest, &mut report, &mut referenced),
+            Err(err) => report.error(format!("could not parse {}: {}", ANIMATION_MANIFEST, err)),
+        },
+        Err(err) => report.error(format!("could not read {}: {}", ANIMATION_MANIFEST, err)),
+    }
+
+    for path in DATA_FILES {
+        referenced.insert(path.to_string());
+        check_data_file(&assets, path, &mut report);
+    }
+
+    for path in OTHER_FILES {
+        if !assets.join(path).is_file() {
+            report.error(format!("missing file {}", path));
+        }
+        referenced.insert(path.to_string());
+    }
+
+    for path in files_in(&assets) {
+        if !referenced.contains(&path) {
+            report.warning(format!("unused file {}", path));
+        }
+    }
+
+    for warning in &report.warnings {
+        println!("warning: {}", warning);
+    }
+    for error in &report.errors {
+        println!("error: {}", error);
+    }
+    println!("{} errors, {} warnings", report.errors.len(), report.warnings.len());
+
+    if report.errors.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+/// Only checks that the file parses, the values are up to the game.
+fn check_data_file(assets: &Path, path: &str, report: &mut Report) {
+    match fs::read_to_string(assets.join(path)) {
+        Ok(contents) => if let Err(err) = ron::from_str::<ron::Value>(&contents) {
+            report.error(format!("could not parse {}: {}", path, err));
+        },
+        Err(err) => report.error(format!("could not read {}: {}", path, err)),
+    }
+}
+
+/// Paths of all the `#[asset(path = "...")]` attributes in the source.
+/// Attributes which are not a plain path are reported, so that their files don't go unchecked.
+fn collection_paths(source: &str, report: &mut Report) -> Vec<String> {
+    let mut paths = Vec::new();
+
+    for (index, _) in source.match_indices("#[asset(") {
+        let arguments = &source[index + "#[asset(".len()..];
+        let path = arguments.trim_start().strip_prefix("path")
+            .and_then(|rest| rest.trim_start().strip_prefix('='))
+            .and_then(|rest| rest.trim_start().strip_prefix('"'))
+            .and_then(|rest| rest.split_once('"'))
+            .map(|(path, _)| path);
+
+        match path {
+            Some(path) => paths.push(path.to_string()),
+            None => {
+                let line = source[..index].matches('\n').count() + 1;
+                report.error(format!("could not read the asset attribute on line {} of src/loading.rs", line));
+            },
+        }
+    }
+
+    paths
+}
+
+fn check_manifest(assets: &Path, manifest: &ManifestFile, report: &mut Report, referenced: &mut BTreeSet<String>) {
+    let mut frame_counts = BTreeMap::new();
+    let mut sizes = BTreeMap::new();
+
+    for entry in &manifest.animations {
+        let name = format!("{} {} {}", entry.species, entry.state, entry.part);
+        if entry.frames.is_empty() {
+            report.error(format!("animation {} has no frames", name));
+            continue;
+        }
+
+        for (frame, marker) in &entry.markers {
+            if *frame >= entry.frames.len() {
+                report.error(format!(
+                    "marker {} of {} is on frame {}, but the animation has only {} frames",
+                    marker, name, frame, entry.frames.len(),
+                ));
+            }
+        }
+
+        let mut animation_size = None;
+        for frame in &entry.frames {
+            referenced.insert(frame.clone());
+
+            let size = match png_size(&assets.join(frame)) {
+                Ok(size) => size,
+                Err(err) => {
+                    report.error(format!("frame {} of {}: {}", frame, name, err));
+                    continue;
+                }
+            };
+
+            match animation_size {
+                None => animation_size = Some(size),
+                Some(expected) if expected != size => report.error(format!(
+                    "frame {} of {} is {}x{}, other frames are {}x{}",
+                    frame, name, size.0, size.1, expected.0, expected.1,
+                )),
+                _ => {},
+            }
+        }
+
+        frame_counts.entry((&entry.state, &entry.part)).or_insert_with(Vec::new).push((&entry.species, entry.frames.len()));
+        let blank = entry.frames.iter().all(|frame| frame == BLANK_FRAME);
+        if let (Some(size), false) = (animation_size, blank) {
+            sizes.entry((&entry.species, &entry.state)).or_insert_with(Vec::new).push((&entry.part, size));
+        }
+    }
+
+    for ((state, part), counts) in &frame_counts {
+        let (_, first) = counts[0];
+        if counts.iter().any(|&(_, count)| count != first) {
+            let counts = counts.iter().map(|(species, count)| format!("{} {}", species, count)).collect::<Vec<_>>();
+            report.warning(format!("species have different frame counts for {} {}: {}", state, part, counts.join(", ")));
+        }
+    }
+
+    // eyes are drawn over the body, so they are expected to be of the same size
+    for ((species, state), parts) in &sizes {
+        let body = parts.iter().find(|(part, _)| part.0 == "Body");
+        let eyes = parts.iter().find(|(part, _)| part.0 == "Eyes");
+        if let (Some((_, body)), Some((_, eyes))) = (body, eyes) {
+            if body != eyes {
+                report.warning(format!(
+                    "{} {} body is {}x{}, but eyes are {}x{}",
+                    species, state, body.0, body.1, eyes.0, eyes.1,
+                ));
+            }
+        }
+    }
+}
+
+/// Width and height from the header of a PNG file.
+fn png_size(path: &Path) -> Result<(u32, u32), String> {
+    let bytes = fs::read(path).map_err(|err| format!("could not read the file: {}", err))?;
+
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    if bytes.len() < 24 || bytes[..8] != SIGNATURE || &bytes[12..16] != b"IHDR" {
+        return Err("not a PNG image".to_string());
+    }
+
+    let width = u32::from_be_bytes([bytes[16], bytes[17], bytes[18], bytes[19]]);
+    let height = u32::from_be_bytes([bytes[20], bytes[21], bytes[22], bytes[23]]);
+    Ok((width, height))
+}
+
+/// All files under the directory, relative to it and with forward slashes like the asset paths.
+fn files_in(dir: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&current) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                files.push(relative.components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"));
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
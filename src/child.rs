@@ -3,8 +3,6 @@ use crate::parents::Species;
 
 /// Size of spawned children.
 pub const CHILD_SIZE: f32 = 130.0;
-/// Size of hitbox of spawned children.
-pub const CHILD_HITBOX_SIZE: f32 = 90.0;
 
 #[derive(Component)]
 pub struct Child {
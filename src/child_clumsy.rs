@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+
+use crate::{growing::Growable, needs::{Bucket, KnockOverBucketEvent, KnockedOver}, parents::Species, species::{BehaviourContext, SpeciesBehaviour, SpeciesBehaviourAppExt}};
+
+/// Growth stage from which derp children start knocking things over.
+pub const CLUMSY_FROM_STAGE: usize = 2;
+pub const CLUMSY_MIN_INTERVAL: f32 = 8.0;
+pub const CLUMSY_MAX_INTERVAL: f32 = 16.0;
+
+/// Every now and then knocks over the nearest bucket.
+#[derive(Component, Debug)]
+pub struct ChildClumsy {
+    timer: Timer,
+}
+
+impl Default for ChildClumsy {
+    fn default() -> Self {
+        ChildClumsy {
+            timer: random_clumsy_timer(),
+        }
+    }
+}
+
+fn random_clumsy_timer() -> Timer {
+    Timer::from_seconds(thread_rng().gen_range(CLUMSY_MIN_INTERVAL..CLUMSY_MAX_INTERVAL), TimerMode::Once)
+}
+
+pub struct ChildClumsyPlugin;
+
+impl Plugin for ChildClumsyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_species_behaviour(Species::Derp, DerpBehaviour)
+            .add_systems(Update, child_clumsy_system.run_if(in_state(crate::GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
+    }
+}
+
+/// Derp children get clumsy once they are big enough to reach the buckets.
+struct DerpBehaviour;
+
+impl SpeciesBehaviour for DerpBehaviour {
+    fn on_stage_change(&self, context: &mut BehaviourContext, stage: usize) {
+        if stage == CLUMSY_FROM_STAGE {
+            context.commands.entity(context.child).insert(ChildClumsy::default());
+        }
+    }
+}
+
+fn child_clumsy_system(
+    time: Res<Time>,
+    mut query: Query<(&mut ChildClumsy, &Growable, &Transform)>,
+    buckets: Query<(Entity, &Transform), (With<Bucket>, Without<KnockedOver>)>,
+    mut knock_over_events: EventWriter<KnockOverBucketEvent>,
+) {
+    for (mut clumsy, growable, transform) in &mut query {
+        // too hypnotized to be clumsy
+        if growable.stopped_by_psycho { continue; }
+
+        clumsy.timer.tick(time.delta());
+        if !clumsy.timer.finished() { continue; }
+        clumsy.timer = random_clumsy_timer();
+
+        let nearest_bucket = buckets.iter()
+            .min_by(|(_, a), (_, b)| {
+                let distance_a = a.translation.distance_squared(transform.translation);
+                let distance_b = b.translation.distance_squared(transform.translation);
+                distance_a.total_cmp(&distance_b)
+            });
+
+        if let Some((bucket, _)) = nearest_bucket {
+            knock_over_events.send(KnockOverBucketEvent { bucket });
+        }
+    }
+}
@@ -1,26 +1,146 @@
 use bevy::prelude::*;
 use rand::{thread_rng, Rng};
 
-use crate::{growing::Growable, hitbox::{Hitbox, InLayers}};
-
-pub const DEFAULT_CHILD_MAX_SPEED: f32 = 60.0;
-pub const CHILD_WALKING_CHANGE: f32 = 50.0;
-
+use crate::{hitbox::{Hitbox, InLayers, Layer}, parents::Species, species::{BehaviourContext, SpeciesBehaviour, SpeciesBehaviourAppExt}};
+
+/// Speed in pixels per second.
+pub const DEFAULT_CHILD_MAX_SPEED: f32 = 80.0;
+/// How quickly a walker can change its velocity, in pixels per second squared.
+pub const DEFAULT_CHILD_MAX_FORCE: f32 = 120.0;
+/// Seconds a walker has to keep pushing against the garden border to count as escaped.
+/// Brushing the border while turning back is not an escape.
+const ESCAPE_TIME: f32 = 1.5;
+
+/// Steering of a walking child.
+/// All the behaviours produce a force, which are weighted, summed up and limited by `max_force`.
 #[derive(Component, Debug)]
 pub struct ChildWalking {
     pub max_speed: f32,
+    pub max_force: f32,
     pub velocity: Vec2,
     pub last_velocity: Vec2,
-    can_move_next_step: bool,
+
+    /// Radius of the circle projected in front of the walker, on which the wander target moves.
+    pub wander_radius: f32,
+    /// How far in front of the walker the wander circle is.
+    pub wander_distance: f32,
+    /// How much the wander target can move along the circle per second, in radians.
+    pub wander_jitter: f32,
+    pub wander_weight: f32,
+
+    /// Other hitboxes closer than this push the walker away.
+    pub separation_radius: f32,
+    pub separation_weight: f32,
+
+    /// Distance from the garden border at which the walker starts turning back.
+    pub boundary_margin: f32,
+    pub boundary_weight: f32,
+
+    /// Place the walker heads to, if any.
+    pub seek_target: Option<Vec2>,
+    pub seek_weight: f32,
+
+    wander_angle: f32,
+    /// Seconds the walker has been held back at the garden border without a break.
+    pinned_time: f32,
+}
+
+/// Sent when a walking child stays pinned to the garden border for `ESCAPE_TIME`, see `ChildWalking::track_escape`.
+#[derive(Event, Debug)]
+pub struct ChildEscapedEvent {
+    pub child: Entity,
 }
 
 impl Default for ChildWalking {
     fn default() -> Self {
         ChildWalking {
             max_speed: DEFAULT_CHILD_MAX_SPEED,
+            max_force: DEFAULT_CHILD_MAX_FORCE,
             velocity: Vec2::ZERO,
             last_velocity: Vec2::ZERO,
-            can_move_next_step: true,
+
+            wander_radius: 40.0,
+            wander_distance: 80.0,
+            wander_jitter: 3.0,
+            wander_weight: 1.0,
+
+            separation_radius: 120.0,
+            separation_weight: 1.5,
+
+            boundary_margin: 80.0,
+            boundary_weight: 3.0,
+
+            seek_target: None,
+            seek_weight: 1.0,
+
+            wander_angle: thread_rng().gen_range(0.0..std::f32::consts::TAU),
+            pinned_time: 0.0,
+        }
+    }
+}
+
+impl ChildWalking {
+    /// The only place deciding what an escape is: being held back at the garden border for `ESCAPE_TIME` in one go.
+    /// Returns true once per escape, in the step the time runs out.
+    fn track_escape(&mut self, pinned: bool, delta: f32) -> bool {
+        if !pinned {
+            self.pinned_time = 0.0;
+            return false;
+        }
+
+        let was_escaped = self.pinned_time >= ESCAPE_TIME;
+        self.pinned_time += delta;
+        !was_escaped && self.pinned_time >= ESCAPE_TIME
+    }
+
+    /// Velocity change needed to go at full speed in the given direction.
+    fn steer_towards(&self, direction: Vec2) -> Vec2 {
+        direction.normalize_or_zero() * self.max_speed - self.velocity
+    }
+
+    fn wander(&mut self, delta: f32) -> Vec2 {
+        self.wander_angle += thread_rng().gen_range(-1.0..1.0) * self.wander_jitter * delta;
+
+        let heading = self.velocity.try_normalize().unwrap_or(Vec2::from_angle(self.wander_angle));
+        let target = heading * self.wander_distance + Vec2::from_angle(self.wander_angle) * self.wander_radius;
+
+        self.steer_towards(target)
+    }
+
+    fn separation(&self, position: Vec2, neighbours: impl Iterator<Item = Vec2>) -> Vec2 {
+        let push = neighbours
+            .map(|neighbour| position - neighbour)
+            .filter(|offset| offset.length() < self.separation_radius)
+            .map(|offset| offset.normalize_or_zero() * (1.0 - offset.length() / self.separation_radius))
+            .sum::<Vec2>();
+
+        if push == Vec2::ZERO {
+            return Vec2::ZERO;
+        }
+
+        self.steer_towards(push) * push.length().min(1.0)
+    }
+
+    fn boundary_avoidance(&self, rect: Rect, garden: Rect) -> Vec2 {
+        // how deep into the margin the walker is on each side, 0 to 1
+        let depth = |distance: f32| ((self.boundary_margin - distance) / self.boundary_margin).clamp(0.0, 1.0);
+
+        let inwards = Vec2::new(
+            depth(rect.min.x - garden.min.x) - depth(garden.max.x - rect.max.x),
+            depth(rect.min.y - garden.min.y) - depth(garden.max.y - rect.max.y),
+        );
+
+        if inwards == Vec2::ZERO {
+            return Vec2::ZERO;
+        }
+
+        self.steer_towards(inwards) * inwards.length().min(1.0)
+    }
+
+    fn seek(&self, position: Vec2) -> Vec2 {
+        match self.seek_target {
+            Some(target) => self.steer_towards(target - position),
+            None => Vec2::ZERO,
         }
     }
 }
@@ -30,66 +150,75 @@ pub struct ChildWalkingPlugin;
 impl Plugin for ChildWalkingPlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_event::<ChildEscapedEvent>()
+            .register_species_behaviour(Species::Poser, PoserBehaviour)
             .add_systems(Update, child_walking_system.run_if(in_state(crate::GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
     }
 }
 
+/// Poser children go for a walk once they grow up.
+struct PoserBehaviour;
+
+impl SpeciesBehaviour for PoserBehaviour {
+    fn on_maturity(&self, context: &mut BehaviourContext) {
+        context.commands.entity(context.child).insert(ChildWalking::default());
+    }
+}
+
 fn child_walking_system(
     time: Res<Time>,
-    mut query: Query<(Option<&mut ChildWalking>, &mut Transform, &Hitbox, &InLayers)>,
+    mut query: Query<(Entity, Option<&mut ChildWalking>, &mut Transform, &Hitbox, &InLayers)>,
+    mut escaped_events: EventWriter<ChildEscapedEvent>,
 ) {
-    for (walking, _, _, _) in &mut query.iter_mut() {
-        let Some(mut walking) = walking else { continue };
+    let delta = time.delta_seconds();
+    if delta == 0.0 { return; }
 
-        walking.can_move_next_step = true;
-        walking.last_velocity = walking.velocity;
-        walking.velocity += time.delta_seconds() * Vec2::from_angle(thread_rng().gen_range(0.0..std::f32::consts::PI * 2.0) as f32) * CHILD_WALKING_CHANGE;
+    // snapshot of where everything is before anybody moves
+    let obstacles = query.iter()
+        .map(|(entity, _, transform, hitbox, layers)| (entity, hitbox.world_rect(transform), layers.layers))
+        .collect::<Vec<_>>();
 
-        if walking.velocity.length() > walking.max_speed {
-            walking.velocity = walking.velocity.normalize() * walking.max_speed;
-        }
-    }
-    let mut combinations = query.iter_combinations_mut::<2>();
-    while let Some([mut one, mut two]) = combinations.fetch_next() {
-        let mut one = &mut one;
-        let mut two = &mut two;
-        for _ in 0..2 {
-            std::mem::swap(&mut one, &mut two);
-            let ((Some(ref mut walking), transform, hitbox, layers), (maybe_other_walking, other_transform, other_hitbox, other_layers)) = (&mut one, &two) else { continue };
-            if !walking.can_move_next_step {
-                continue;
-            }
-
-            let mut next_transform = transform.clone();
-            next_transform.translation += walking.velocity.extend(0.0);
+    let garden = obstacles.iter()
+        .filter(|(_, _, layers)| layers.contains(Layer::Garden))
+        .map(|(_, rect, _)| *rect)
+        .reduce(|a, b| a.union(b));
 
-            let mut other_next_transform = (*other_transform).clone();
-            if let Some(other_walking) = maybe_other_walking {
-                other_next_transform.translation += other_walking.velocity.extend(0.0);
-            }
+    for (entity, walking, mut transform, hitbox, layers) in &mut query {
+        let Some(mut walking) = walking else { continue };
 
-            let share_layers = layers.intersects(other_layers);
-            let overlap_hitboxes = hitbox.intersects(other_hitbox, &next_transform, &*other_transform);
-            let overlap_hitboxes_next = hitbox.intersects(other_hitbox, &next_transform, &other_next_transform);
+        let rect = hitbox.world_rect(&transform);
+        let position = rect.center();
 
-            let is_other_garden = other_layers.contains(crate::hitbox::Layer::Garden);
-            let is_contained_in_other = other_hitbox.contains_entirely(hitbox, &*other_transform, &next_transform);
+        let neighbours = obstacles.iter()
+            .filter(|(other, _, other_layers)| *other != entity && layers.intersects_layer_set(*other_layers))
+            .map(|(_, other_rect, _)| other_rect.center());
 
-            let bounced_off_other = share_layers && (overlap_hitboxes || overlap_hitboxes_next);
-            let bounced_off_garden_border = is_other_garden && !is_contained_in_other;
+        let mut force = walking.wander(delta) * walking.wander_weight
+            + walking.separation(position, neighbours) * walking.separation_weight
+            + walking.seek(position) * walking.seek_weight;
 
-            if bounced_off_other || bounced_off_garden_border {
-                walking.velocity = -walking.velocity;
-                walking.can_move_next_step = false;
-            }
+        if let Some(garden) = garden {
+            force += walking.boundary_avoidance(rect, garden) * walking.boundary_weight;
         }
-    }
 
-    for (walking, mut transform, _, _) in &mut query {
-        if let Some(walking) = walking {
-            if walking.can_move_next_step {
-                transform.translation += walking.velocity.extend(0.0);
+        let force = force.clamp_length_max(walking.max_force);
+
+        walking.last_velocity = walking.velocity;
+        walking.velocity = (walking.velocity + force * delta).clamp_length_max(walking.max_speed);
+        transform.translation += (walking.velocity * delta).extend(0.0);
+
+        // steering is soft, so make sure nobody actually leaves the garden
+        if let Some(garden) = garden {
+            let rect = hitbox.world_rect(&transform);
+            let correction = Vec2::new(
+                (garden.min.x - rect.min.x).max(0.0) + (garden.max.x - rect.max.x).min(0.0),
+                (garden.min.y - rect.min.y).max(0.0) + (garden.max.y - rect.max.y).min(0.0),
+            );
+            transform.translation += correction.extend(0.0);
+
+            if walking.track_escape(correction != Vec2::ZERO, delta) {
+                escaped_events.send(ChildEscapedEvent { child: entity });
             }
         }
     }
-}
\ No newline at end of file
+}
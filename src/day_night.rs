@@ -0,0 +1,200 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::{garden::Garden, localization::Localization, parents::Species, tween::lerp_color, GameState};
+
+/// Hour of the day at which the shift starts.
+const SHIFT_START_HOUR: f32 = 6.0;
+const NIGHT_GARDEN_TINT: Color = Color::rgb(0.35, 0.4, 0.65);
+const NIGHT_CLEAR_COLOR: Color = Color::rgb(0.15, 0.13, 0.25);
+const CLOCK_COLOR: Color = Color::WHITE;
+
+pub struct DayNightPlugin {
+    /// Length of the whole day in seconds.
+    pub cycle_length: f32,
+}
+
+/// Part of the day, as shown on the clock.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum DayPhase {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+impl DayPhase {
+    /// Key of the name in the string tables.
+    fn label_key(&self) -> &'static str {
+        match self {
+            DayPhase::Dawn => "day.dawn",
+            DayPhase::Day => "day.day",
+            DayPhase::Dusk => "day.dusk",
+            DayPhase::Night => "day.night",
+        }
+    }
+}
+
+/// Whether a species prefers to grow at night or during the day.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Chronotype {
+    Nocturnal,
+    Diurnal,
+}
+
+impl Chronotype {
+    /// Mushrooms like the dark, except for posers, who like to be seen.
+    pub fn of(species: Species) -> Self {
+        match species {
+            Species::Derp | Species::Psycho => Chronotype::Nocturnal,
+            Species::Poser => Chronotype::Diurnal,
+        }
+    }
+
+    /// Growth speed multipliers in full darkness and in full daylight.
+    fn growth_mults(&self) -> (f32, f32) {
+        match self {
+            Chronotype::Nocturnal => (1.4, 0.75),
+            Chronotype::Diurnal => (0.75, 1.3),
+        }
+    }
+}
+
+/// Time of day of the current shift.
+#[derive(Resource, Debug)]
+pub struct DayNightCycle {
+    /// Length of the whole day in seconds.
+    length: f32,
+    /// Time since the start of the shift in seconds.
+    elapsed: f32,
+    /// Clear color of the daytime, restored when leaving the game.
+    day_clear_color: Color,
+}
+
+impl DayNightCycle {
+    /// Hour of the day, 0 to 24.
+    pub fn hour(&self) -> f32 {
+        (SHIFT_START_HOUR + self.elapsed / self.length * 24.0) % 24.0
+    }
+
+    pub fn phase(&self) -> DayPhase {
+        match self.hour() {
+            hour if (5.0..8.0).contains(&hour) => DayPhase::Dawn,
+            hour if (8.0..17.0).contains(&hour) => DayPhase::Day,
+            hour if (17.0..20.0).contains(&hour) => DayPhase::Dusk,
+            _ => DayPhase::Night,
+        }
+    }
+
+    /// How bright it is, 0 at night to 1 during the day.
+    pub fn daylight(&self) -> f32 {
+        let sun_height = ((self.hour() - 6.0) / 12.0 * PI).sin();
+        let t = ((sun_height + 0.25) / 0.5).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Multiplies the growth speed of the children of the species.
+    pub fn growth_mult(&self, species: Species) -> f32 {
+        let (night, day) = Chronotype::of(species).growth_mults();
+        night + (day - night) * self.daylight()
+    }
+}
+
+#[derive(Component, Debug)]
+struct ClockText;
+
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(DayNightCycle {
+                length: self.cycle_length,
+                elapsed: 0.0,
+                day_clear_color: Color::BLACK,
+            })
+            .add_systems(OnEnter(GameState::Playing), (reset_cycle, setup_clock))
+            .add_systems(Update, (
+                advance_cycle,
+                tint_world,
+                update_clock,
+            ).chain().run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))))
+            .add_systems(OnExit(GameState::Playing), (restore_clear_color, cleanup_clock));
+    }
+}
+
+fn reset_cycle(
+    mut cycle: ResMut<DayNightCycle>,
+    clear_color: Res<ClearColor>,
+) {
+    cycle.elapsed = 0.0;
+    cycle.day_clear_color = clear_color.0;
+}
+
+fn advance_cycle(
+    time: Res<Time>,
+    mut cycle: ResMut<DayNightCycle>,
+) {
+    cycle.elapsed += time.delta_seconds();
+}
+
+fn tint_world(
+    cycle: Res<DayNightCycle>,
+    mut clear_color: ResMut<ClearColor>,
+    mut garden_query: Query<&mut Sprite, With<Garden>>,
+) {
+    let darkness = 1.0 - cycle.daylight();
+
+    clear_color.0 = lerp_color(cycle.day_clear_color, NIGHT_CLEAR_COLOR, darkness);
+    for mut sprite in &mut garden_query {
+        sprite.color = lerp_color(Color::WHITE, NIGHT_GARDEN_TINT, darkness);
+    }
+}
+
+fn restore_clear_color(
+    cycle: Res<DayNightCycle>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    clear_color.0 = cycle.day_clear_color;
+}
+
+fn setup_clock(mut commands: Commands) {
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: 40.0,
+                    color: CLOCK_COLOR,
+                    ..default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(40.0),
+                top: Val::Px(60.0),
+                ..default()
+            },
+            ..default()
+        },
+        ClockText,
+    ));
+}
+
+fn update_clock(
+    cycle: Res<DayNightCycle>,
+    mut query: Query<&mut Text, With<ClockText>>,
+    localization: Res<Localization>,
+) {
+    let hour = cycle.hour();
+    let minutes = (hour.fract() * 60.0) as u32;
+
+    for mut text in &mut query {
+        text.sections[0].value = format!("{:02}:{:02} {}", hour as u32, minutes, localization.text(cycle.phase().label_key()));
+    }
+}
+
+fn cleanup_clock(mut commands: Commands, query: Query<Entity, With<ClockText>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,15 +1,10 @@
 use bevy::prelude::*;
 
-use crate::GameState;
-
-/// Time which takes to scale difficulties from start to end values.
-const DIFFICULTY_SCALING_TIME: f32 = 5.0 * 60.0;
+use crate::{tuning::Tuning, GameState};
 
+/// Defaults of the `Tuning`, which holds the start and end values of the difficulty.
 pub const START_PATIENCE: f32 = 120.0;
-const END_PATIENCE: f32 = 35.0;
-
 pub const START_PARENT_SPAWN_TIME: f32 = 15.0;
-const END_PARENT_SPAWN_TIME: f32 = 5.0;
 
 pub struct DifficultyPlugin;
 
@@ -17,6 +12,8 @@ pub struct DifficultyPlugin;
 pub struct Difficulty {
     /// Elapsed time from the start of the game in seconds.
     elapsed_time: f32,
+    /// Time which takes to scale difficulties from start to end values.
+    scaling_time: f32,
     /// Patience which will be asigned to parents upon spawning.
     pub parent_patience: f32,
     // Time until next parent spawns.
@@ -34,10 +31,18 @@ impl Plugin for DifficultyPlugin {
     }
 }
 
+impl Difficulty {
+    /// How far the difficulty scaled from start to end values, 0 to 1.
+    pub fn progress(&self) -> f32 {
+        self.elapsed_time / self.scaling_time
+    }
+}
+
 impl Default for Difficulty {
     fn default() -> Self {
         Self { 
             elapsed_time: 0.0,
+            scaling_time: Tuning::default().difficulty_scaling_time,
             parent_patience: START_PATIENCE,
             parent_spawn_time: START_PARENT_SPAWN_TIME,
         }
@@ -46,28 +51,36 @@ impl Default for Difficulty {
 
 fn reset_difficulty(
     mut difficulty: ResMut<Difficulty>,
+    tuning: Res<Tuning>,
 ) {
-    *difficulty = Difficulty::default();
+    *difficulty = Difficulty {
+        elapsed_time: 0.0,
+        scaling_time: tuning.difficulty_scaling_time,
+        parent_patience: tuning.start_patience,
+        parent_spawn_time: tuning.start_parent_spawn_time,
+    };
 }
 
 fn update_difficulty(
     time: Res<Time>,
+    tuning: Res<Tuning>,
     mut difficulty: ResMut<Difficulty>,
 ) {
+    difficulty.scaling_time = tuning.difficulty_scaling_time;
     difficulty.elapsed_time += time.delta_seconds();
 
-    if difficulty.elapsed_time > DIFFICULTY_SCALING_TIME {
-        difficulty.elapsed_time = DIFFICULTY_SCALING_TIME;
+    if difficulty.elapsed_time > difficulty.scaling_time {
+        difficulty.elapsed_time = difficulty.scaling_time;
     }
-    let percentage = difficulty.elapsed_time / DIFFICULTY_SCALING_TIME;
+    let percentage = difficulty.elapsed_time / difficulty.scaling_time;
 
-    difficulty.parent_patience = (END_PATIENCE - START_PATIENCE) * percentage + START_PATIENCE;
-    difficulty.parent_spawn_time = (END_PARENT_SPAWN_TIME - START_PARENT_SPAWN_TIME) * percentage + START_PARENT_SPAWN_TIME;
+    difficulty.parent_patience = (tuning.end_patience - tuning.start_patience) * percentage + tuning.start_patience;
+    difficulty.parent_spawn_time = (tuning.end_parent_spawn_time - tuning.start_parent_spawn_time) * percentage + tuning.start_parent_spawn_time;
 }
 
 /*
 
-grow time: GROW_DURATION * GROW_SPEED * GROW_STAGES (25)
+grow time: grow_duration * grow_speed * GROW_STAGES (25)
 
 nejmelnsi stihnutelnej grow - 35
 
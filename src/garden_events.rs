@@ -0,0 +1,312 @@
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, thread_rng, Rng};
+
+use crate::{difficulty::Difficulty, garden::Garden, hitbox::Hitbox, loading::TextureAssets, localization::Localization, needs::Needs, particles::{spawn_particles, ParticleEmitter}, tuning::Tuning, GameState};
+
+/// Time between two garden events at the start and at the end of the difficulty scaling.
+const START_EVENT_INTERVAL: f32 = 60.0;
+const END_EVENT_INTERVAL: f32 = 30.0;
+
+const WORM_SWARM_RADIUS: f32 = 250.0;
+const WORM_SWARM_WORMS: usize = 10;
+const WORM_SIZE: Vec2 = Vec2::splat(48.0);
+
+const OVERLAY_Z: f32 = 30.0;
+const BANNER_COLOR: Color = Color::rgb(0.9, 0.95, 1.0);
+
+pub struct GardenEventsPlugin;
+
+/// Something unexpected happening in the garden for a while.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum GardenEvent {
+    /// Every child gets all the water it needs.
+    Rain,
+    /// Children get thirsty twice as fast.
+    Drought,
+    /// Children in one part of the garden get fed.
+    WormSwarm,
+    /// Children grow faster, but also get hungry and thirsty faster.
+    HeatWave,
+}
+
+const GARDEN_EVENTS: [GardenEvent; 4] = [
+    GardenEvent::Rain,
+    GardenEvent::Drought,
+    GardenEvent::WormSwarm,
+    GardenEvent::HeatWave,
+];
+
+impl GardenEvent {
+    /// Helpful events get rarer and harmful ones more common as the difficulty goes up.
+    fn weight(&self, difficulty: f32) -> f32 {
+        match self {
+            GardenEvent::Rain => 3.0 - 2.0 * difficulty,
+            GardenEvent::Drought => 1.0 + 2.0 * difficulty,
+            GardenEvent::WormSwarm => 3.0 - 2.0 * difficulty,
+            GardenEvent::HeatWave => 1.0 + difficulty,
+        }
+    }
+
+    fn duration(&self) -> f32 {
+        match self {
+            GardenEvent::Rain => 8.0,
+            GardenEvent::Drought => 25.0,
+            GardenEvent::WormSwarm => 10.0,
+            GardenEvent::HeatWave => 20.0,
+        }
+    }
+
+    /// Key of the banner in the string tables.
+    fn banner_key(&self) -> &'static str {
+        match self {
+            GardenEvent::Rain => "events.rain",
+            GardenEvent::Drought => "events.drought",
+            GardenEvent::WormSwarm => "events.worm_swarm",
+            GardenEvent::HeatWave => "events.heat_wave",
+        }
+    }
+
+    /// Tint of the whole screen while the event lasts.
+    fn overlay_color(&self) -> Option<Color> {
+        match self {
+            GardenEvent::Rain => Some(Color::rgba(0.2, 0.3, 0.8, 0.2)),
+            GardenEvent::Drought => Some(Color::rgba(0.9, 0.7, 0.2, 0.15)),
+            GardenEvent::WormSwarm => None,
+            GardenEvent::HeatWave => Some(Color::rgba(1.0, 0.3, 0.1, 0.15)),
+        }
+    }
+
+    fn conditions(&self) -> GardenConditions {
+        match self {
+            GardenEvent::Drought => GardenConditions {
+                thirst_rate_mult: 2.0,
+                ..default()
+            },
+            GardenEvent::HeatWave => GardenConditions {
+                hunger_rate_mult: 1.5,
+                thirst_rate_mult: 1.5,
+                growth_speed_mult: 1.5,
+            },
+            _ => GardenConditions::default(),
+        }
+    }
+}
+
+/// Modifiers of the children's needs and growth caused by the current garden event.
+#[derive(Resource, Debug)]
+pub struct GardenConditions {
+    pub hunger_rate_mult: f32,
+    pub thirst_rate_mult: f32,
+    pub growth_speed_mult: f32,
+}
+
+impl Default for GardenConditions {
+    fn default() -> Self {
+        GardenConditions {
+            hunger_rate_mult: 1.0,
+            thirst_rate_mult: 1.0,
+            growth_speed_mult: 1.0,
+        }
+    }
+}
+
+struct ActiveGardenEvent {
+    event: GardenEvent,
+    timer: Timer,
+    /// Where the worms are, for the worm swarm.
+    center: Vec2,
+}
+
+#[derive(Resource)]
+struct GardenEventSchedule {
+    next_event: Timer,
+    active: Option<ActiveGardenEvent>,
+}
+
+impl Default for GardenEventSchedule {
+    fn default() -> Self {
+        GardenEventSchedule {
+            next_event: Timer::from_seconds(START_EVENT_INTERVAL, TimerMode::Once),
+            active: None,
+        }
+    }
+}
+
+/// Visuals of the current garden event, despawned when it ends.
+#[derive(Component, Debug)]
+struct GardenEventVisual;
+
+#[derive(Component, Debug)]
+struct GardenEventBanner;
+
+impl Plugin for GardenEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GardenConditions>()
+            .init_resource::<GardenEventSchedule>()
+            .add_systems(OnEnter(GameState::Playing), reset_garden_events)
+            .add_systems(Update, (
+                start_garden_events,
+                apply_garden_events,
+                end_garden_events,
+            ).chain().run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))))
+            .add_systems(OnExit(GameState::Playing), cleanup_garden_event_banner);
+    }
+}
+
+fn reset_garden_events(
+    mut schedule: ResMut<GardenEventSchedule>,
+    mut conditions: ResMut<GardenConditions>,
+) {
+    *schedule = GardenEventSchedule::default();
+    *conditions = GardenConditions::default();
+}
+
+#[allow(clippy::too_many_arguments)]
+fn start_garden_events(
+    mut commands: Commands,
+    time: Res<Time>,
+    textures: Res<TextureAssets>,
+    difficulty: Res<Difficulty>,
+    mut schedule: ResMut<GardenEventSchedule>,
+    mut conditions: ResMut<GardenConditions>,
+    garden_query: Query<(&Hitbox, &Transform), With<Garden>>,
+    localization: Res<Localization>,
+) {
+    if schedule.active.is_some() { return; }
+
+    schedule.next_event.tick(time.delta());
+    if !schedule.next_event.finished() { return; }
+
+    let progress = difficulty.progress();
+    let Ok(event) = GARDEN_EVENTS.choose_weighted(&mut thread_rng(), |event| event.weight(progress)).copied() else { return };
+
+    let Ok((hitbox, transform)) = garden_query.get_single() else { return };
+    let garden = hitbox.world_rect(transform);
+    let center = Vec2::new(
+        thread_rng().gen_range(garden.min.x..garden.max.x),
+        thread_rng().gen_range(garden.min.y..garden.max.y),
+    );
+
+    info!("garden event: {:?}", event);
+    *conditions = event.conditions();
+
+    if let Some(color) = event.overlay_color() {
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(Vec3::Z * OVERLAY_Z),
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::new(crate::WINDOW_WIDTH, crate::WINDOW_HEIGHT)),
+                    ..default()
+                },
+                ..default()
+            },
+            GardenEventVisual,
+            crate::GameObject,
+        ));
+    }
+
+    if event == GardenEvent::Rain {
+        let rain = spawn_particles(&mut commands, Vec2::new(0.0, crate::WINDOW_HEIGHT / 2.0 + 50.0), ParticleEmitter::rain());
+        commands.entity(rain).insert(GardenEventVisual);
+    }
+
+    if event == GardenEvent::WormSwarm {
+        for _ in 0..WORM_SWARM_WORMS {
+            let offset = Vec2::from_angle(thread_rng().gen_range(0.0..std::f32::consts::TAU))
+                * thread_rng().gen_range(0.0..WORM_SWARM_RADIUS);
+            commands.spawn((
+                SpriteBundle {
+                    texture: textures.worm.clone(),
+                    transform: Transform::from_translation((center + offset).extend(-1.0)),
+                    sprite: Sprite {
+                        custom_size: Some(WORM_SIZE),
+                        flip_x: thread_rng().gen_bool(0.5),
+                        ..default()
+                    },
+                    ..default()
+                },
+                GardenEventVisual,
+                crate::GameObject,
+            ));
+        }
+    }
+
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                localization.text(event.banner_key()),
+                TextStyle {
+                    font_size: 36.0,
+                    color: BANNER_COLOR,
+                    ..default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(70.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        },
+        GardenEventBanner,
+    ));
+
+    schedule.active = Some(ActiveGardenEvent {
+        event,
+        timer: Timer::from_seconds(event.duration(), TimerMode::Once),
+        center,
+    });
+}
+
+fn apply_garden_events(
+    mut commands: Commands,
+    schedule: Res<GardenEventSchedule>,
+    mut needs_query: Query<(&mut Needs, &Transform)>,
+    tuning: Res<Tuning>,
+) {
+    let Some(active) = &schedule.active else { return };
+
+    for (mut needs, transform) in &mut needs_query {
+        match active.event {
+            GardenEvent::Rain => needs.quench(&mut commands, &tuning),
+            GardenEvent::WormSwarm if transform.translation.truncate().distance(active.center) < WORM_SWARM_RADIUS => {
+                needs.feed(&mut commands, &tuning);
+            },
+            _ => {},
+        }
+    }
+}
+
+fn end_garden_events(
+    mut commands: Commands,
+    time: Res<Time>,
+    difficulty: Res<Difficulty>,
+    mut schedule: ResMut<GardenEventSchedule>,
+    mut conditions: ResMut<GardenConditions>,
+    visual_query: Query<Entity, Or<(With<GardenEventVisual>, With<GardenEventBanner>)>>,
+) {
+    let Some(active) = schedule.active.as_mut() else { return };
+
+    active.timer.tick(time.delta());
+    if !active.timer.finished() { return; }
+
+    schedule.active = None;
+    *conditions = GardenConditions::default();
+
+    let interval = START_EVENT_INTERVAL + (END_EVENT_INTERVAL - START_EVENT_INTERVAL) * difficulty.progress();
+    schedule.next_event = Timer::from_seconds(interval, TimerMode::Once);
+
+    for entity in &visual_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn cleanup_garden_event_banner(mut commands: Commands, query: Query<Entity, With<GardenEventBanner>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,11 +1,30 @@
 use bevy::{prelude::*, sprite::Anchor};
 
-use crate::{animations::{Animation, AnimationBundle}, child::{Child, EyesVisual, CHILD_SIZE}, child_walking::ChildWalking, hitbox::{Draggable, DropEvent, Hitbox}, loading::{AnimationAssets, TextureAssets}, pulsing::Pulsing, GameState};
+use crate::{animations::{Animation, AnimationBundle, AnimationFinished, AnimationFrameEvent, AnimationMarker, AnimationPart, AnimationState}, child::{Child, EyesVisual, CHILD_SIZE}, child_walking::ChildWalking, day_night::DayNightCycle, garden_events::GardenConditions, hitbox::{Draggable, DropEvent, Hitbox}, loading::AnimationAssets, particles::{spawn_particles, ParticleEmitter}, pulsing::Pulsing, tuning::Tuning, tween::Tween, world_bar::{WorldBar, WorldBarBundle}, GameState};
 use crate::parents::Species;
+use crate::species::{BehaviourContext, ChildGrowthEvent, GrowthStep, SpeciesBehaviour, SpeciesBehaviourAppExt};
 
-pub const GROW_SPEED: f32 = 1.0;
-pub const GROW_DURATION: f32 = 5.0;
 pub const GROW_STAGES: usize = 5;
+/// Animations of the children in each stage.
+const GROW_STAGE_STATES: [AnimationState; GROW_STAGES] = [
+    AnimationState::Spores,
+    AnimationState::Baby,
+    AnimationState::Child,
+    AnimationState::Teenager,
+    AnimationState::Grown,
+];
+
+pub const HYPNO_RANGE: f32 = 500.0;
+const HYPNO_AURA_COLOR: Color = Color::rgba(0.7, 0.2, 0.9, 0.6);
+/// Where the little swirl above a hypnotized child is.
+const HYPNOTIZED_MARKER_OFFSET: Vec2 = Vec2::new(0.0, CHILD_SIZE + 20.0);
+const HYPNOTIZED_MARKER_SCALE: f32 = 0.06;
+/// How much the swirls throb when a psycho blinks.
+const HYPNOTIZED_MARKER_THROB: f32 = 0.2;
+const GROWTH_BAR_SIZE: Vec2 = Vec2::new(70.0, 10.0);
+const GROWTH_BAR_COLOR: Color = Color::rgb(0.45, 0.8, 0.35);
+/// Space between the bottom of the child and its growth bar.
+const GROWTH_BAR_GAP: f32 = 10.0;
 
 pub struct GrowingPlugin;
 
@@ -13,8 +32,6 @@ pub struct GrowingPlugin;
 pub struct Growable {
     progress: f32,
     pub stage: usize,
-    // TODO: add eyes as second item in pair?
-    _textures: [(Handle<Image>, ); GROW_STAGES],
     /// Determine if growing is currently stopped.
     pub stopped_by_needs: bool,
     pub stopped_by_psycho: bool,
@@ -22,43 +39,10 @@ pub struct Growable {
 }
 
 impl Growable {
-    pub fn derp(textures: &TextureAssets) -> Self {
-        Self {
-            _textures: [
-                (textures.derp_spores.clone(), ),
-                (textures.derp_baby_body.clone(), ),
-                (textures.derp_child_body.clone(), ),
-                (textures.derp_teenager_body.clone(), ),
-                (textures.derp_parent_body.clone(), ),
-            ],
-            ..default()
-        }
-    }
-
-    pub fn psycho(textures: &TextureAssets) -> Self {
-        Self {
-            _textures: [
-                (textures.psycho_spores.clone(), ),
-                (textures.psycho_baby_body.clone(), ),
-                (textures.psycho_child_body.clone(), ),
-                (textures.psycho_teenager_body.clone(), ),
-                (textures.psycho_parent_body.clone(), ),
-            ],
-            ..default()
-        }
-    }
-
-    pub fn poser(textures: &TextureAssets) -> Self {
-        Self {
-            _textures: [
-                (textures.poser_spores.clone(), ),
-                (textures.poser_baby_body.clone(), ),
-                (textures.poser_child_body.clone(), ),
-                (textures.poser_teenager_body.clone(), ),
-                (textures.poser_parent_body.clone(), ),
-            ],
-            ..default()
-        }
+    /// How far the child is from growing up, from 0 when planted to 1.
+    pub fn growth_fraction(&self, grow_duration: f32) -> f32 {
+        let stage_fraction = if grow_duration > 0.0 { (self.progress / grow_duration).min(1.0) } else { 0.0 };
+        (self.stage as f32 + stage_fraction) / (GROW_STAGES - 1) as f32
     }
 }
 
@@ -66,12 +50,20 @@ impl Plugin for GrowingPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_event::<HypnoDespawnEvent>()
+            .add_event::<HypnosisStartedEvent>()
+            .init_gizmo_group::<HypnoGizmos>()
+            .register_species_behaviour(Species::Psycho, PsychoBehaviour)
+            .add_systems(Startup, configure_hypno_gizmos)
+            .add_systems(Update, draw_hypno_auras.run_if(in_state(GameState::Playing)))
             .add_systems(Update, (
                 progress_grow,
                 read_on_drop_events,
                 read_hypno_despawn_events,
                 update_hypnotism,
+                despawn_unwound_markers,
+                throb_hypnotized_markers,
                 update_child_visual,
+                update_growth_bars,
             ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused)))
         );
     }
@@ -79,21 +71,31 @@ impl Plugin for GrowingPlugin {
 
 fn progress_grow(
     mut commands: Commands,
-    animation_assets: Res<AnimationAssets>,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Growable, &mut Draggable, &Child, &mut Sprite, &mut Hitbox)>,
+    mut query: Query<(Entity, &mut Growable, &mut Draggable, &Child, &mut Sprite, &mut Hitbox, &Transform)>,
+    mut growth_events: EventWriter<ChildGrowthEvent>,
+    conditions: Res<GardenConditions>,
+    cycle: Res<DayNightCycle>,
+    tuning: Res<Tuning>,
 ) {
-    for (entity, mut growable, mut draggable, child, mut sprite, mut hitbox) in &mut query {
+    for (entity, mut growable, mut draggable, child, mut sprite, mut hitbox, transform) in &mut query {
         if growable.stopped_by_psycho || growable.stopped_by_needs || growable.stage == GROW_STAGES - 1 {
             continue;
         }
 
-        growable.progress += time.delta_seconds() * GROW_SPEED;
+        growable.progress += time.delta_seconds() * tuning.grow_speed * conditions.growth_speed_mult * cycle.growth_mult(child.species);
 
-        if growable.progress >= GROW_DURATION {
-            growable.progress -= GROW_DURATION;
+        if growable.progress >= tuning.grow_duration {
+            growable.progress -= tuning.grow_duration;
             growable.stage += 1;
             growable.is_changed = true;
+            spawn_particles(&mut commands, transform.translation.truncate(), ParticleEmitter::sparkles());
+
+            growth_events.send(ChildGrowthEvent {
+                child: entity,
+                species: child.species,
+                step: GrowthStep::StageChanged(growable.stage),
+            });
 
             if growable.stage == 1 {
                 hitbox.rect.min.y += CHILD_SIZE / 4.0;
@@ -106,27 +108,23 @@ fn progress_grow(
             if growable.stage == GROW_STAGES - 1 {
                 draggable.special_allowed_entities.push(child.parent_entity);
 
-                
-                if child.species == Species::Psycho {
-                    add_hypnotic_behaviour(&mut commands, entity, &animation_assets);
-                }
-
                 commands.entity(entity).insert(Pulsing {
                     min: 0.95,
                     max: 1.05,
                     speed: 0.2,
-                    ..default()
                 });
-                if child.species == Species::Poser {
-                    commands.entity(entity).insert(ChildWalking::default());
-                }
+
+                growth_events.send(ChildGrowthEvent {
+                    child: entity,
+                    species: child.species,
+                    step: GrowthStep::Matured,
+                });
             }
         }
     }
 }
 
 fn update_child_visual(
-    texture_assets: Res<TextureAssets>,
     animation_assets: Res<AnimationAssets>,
     mut child_query: Query<(Entity, &Child, &mut Growable, &mut Animation, &mut Sprite)>,
     children_query: Query<&Children>,
@@ -142,19 +140,9 @@ fn update_child_visual(
 
         if !growable.is_changed { continue; }
 
-        let mut body_frames = get_child_frames(
-            &texture_assets,
-            mushroom_child.species,
-            growable.stage,
-            false
-        );
-
-        let mut eyes_frames = get_child_frames(
-            &texture_assets,
-            mushroom_child.species,
-            growable.stage,
-            true
-        );
+        let state = GROW_STAGE_STATES[growable.stage.min(GROW_STAGES - 1)];
+        let mut body_frames = animation_assets.get(mushroom_child.species, state, AnimationPart::Body);
+        let mut eyes_frames = animation_assets.get(mushroom_child.species, state, AnimationPart::Eyes);
 
 
         sprite.flip_x = false;
@@ -167,13 +155,13 @@ fn update_child_visual(
                 }
             }
 
-            body_frames = animation_assets.poser_parent_walking_body.clone();
-            eyes_frames = animation_assets.poser_parent_walking_eyes.clone();
+            body_frames = animation_assets.get(Species::Poser, AnimationState::Walking, AnimationPart::Body);
+            eyes_frames = animation_assets.get(Species::Poser, AnimationState::Walking, AnimationPart::Eyes);
         }
 
         if mushroom_child.species == Species::Psycho && growable.stage == 4 {
-            body_frames = animation_assets.psycho_child_hypnotic_body.clone();
-            eyes_frames = animation_assets.psycho_child_hypnotic_eyes.clone();
+            body_frames = animation_assets.get(Species::Psycho, AnimationState::Hypnotic, AnimationPart::Body);
+            eyes_frames = animation_assets.get(Species::Psycho, AnimationState::Hypnotic, AnimationPart::Eyes);
         }
 
         animation.change_frames(body_frames);
@@ -191,137 +179,237 @@ fn update_child_visual(
 }
 
 
-fn get_child_frames(texture_assets: &TextureAssets, species: Species, growth: usize, eyes: bool) -> Vec<Handle<Image>> {
-    match species {
-        Species::Derp => get_child_frames_derp(texture_assets, growth, eyes),
-        Species::Psycho => get_child_frames_psycho(texture_assets, growth, eyes),
-        Species::Poser => get_child_frames_poser(texture_assets, growth, eyes),
-    }
-}
-
-fn get_child_frames_derp(texture_assets: &TextureAssets, growth: usize, eyes: bool) -> Vec<Handle<Image>> {
-    match growth {
-        0 => if eyes { vec![ texture_assets.nothing.clone()] }
-                else { vec![ texture_assets.derp_spores.clone()] },
-        1 => if eyes { vec![ texture_assets.derp_baby_eyes.clone()] }
-                else { vec![ texture_assets.derp_baby_body.clone()] },
-        2 => if eyes { vec![ texture_assets.derp_child_eyes.clone()] }
-                else { vec![ texture_assets.derp_child_body.clone()] },
-        3 => if eyes { vec![ texture_assets.derp_teenager_eyes.clone()] }
-                else { vec![ texture_assets.derp_teenager_body.clone()] },
-        4 => if eyes { vec![ texture_assets.derp_parent_eyes.clone()] }
-                else { vec![ texture_assets.derp_parent_body.clone()] },
-
-        _ => if eyes { vec![ texture_assets.derp_baby_eyes.clone()] }
-                else { vec![ texture_assets.derp_baby_body.clone()] },
-    }
-}
-
-fn get_child_frames_psycho(texture_assets: &TextureAssets, growth: usize, eyes: bool) -> Vec<Handle<Image>> {
-    match growth {
-        0 => if eyes { vec![ texture_assets.nothing.clone()] }
-                else { vec![ texture_assets.psycho_spores.clone()] },
-        1 => if eyes { vec![ texture_assets.psycho_baby_eyes.clone()] }
-                else { vec![ texture_assets.psycho_baby_body.clone()] },
-        2 => if eyes { vec![ texture_assets.psycho_child_eyes.clone()] }
-                else { vec![ texture_assets.psycho_child_body.clone()] },
-        3 => if eyes { vec![ texture_assets.psycho_teenager_eyes.clone()] }
-                else { vec![ texture_assets.psycho_teenager_body.clone()] },
-        4 => if eyes { vec![ texture_assets.psycho_parent_eyes.clone()] }
-                else { vec![ texture_assets.psycho_parent_body.clone()] },
-
-        _ => if eyes { vec![ texture_assets.psycho_baby_eyes.clone()] }
-                else { vec![ texture_assets.psycho_baby_body.clone()] },
-    }
-}
-
-fn get_child_frames_poser(texture_assets: &TextureAssets, growth: usize, eyes: bool) -> Vec<Handle<Image>> {
-    match growth {
-        0 => if eyes { vec![ texture_assets.nothing.clone()] }
-                else { vec![ texture_assets.poser_spores.clone()] },
-        1 => if eyes { vec![ texture_assets.poser_baby_eyes.clone()] }
-                else { vec![ texture_assets.poser_baby_body.clone()] },
-        2 => if eyes { vec![ texture_assets.poser_child_eyes.clone()] }
-                else { vec![ texture_assets.poser_child_body.clone()] },
-        3 => if eyes { vec![ texture_assets.poser_teenager_eyes.clone()] }
-                else { vec![ texture_assets.poser_teenager_body.clone()] },
-        4 => if eyes { vec![ texture_assets.poser_parent_eyes.clone()] }
-                else { vec![ texture_assets.poser_parent_body.clone()] },
-
-        _ => if eyes { vec![ texture_assets.poser_baby_eyes.clone()] }
-                else { vec![ texture_assets.poser_baby_body.clone()] },
-    }
-}
-
 fn read_on_drop_events(
     mut commands: Commands,
     mut events: EventReader<DropEvent>,
-    texture_assets: Res<TextureAssets>,
-    mut query: Query<(&Child, &mut Transform), Without<Growable>>
+    mut query: Query<(&Child, &mut Transform), Without<Growable>>,
+    mut growth_events: EventWriter<ChildGrowthEvent>,
+    tuning: Res<Tuning>,
 ) {
     for event in events.read() {
         if let Ok((child, mut transform)) = query.get_mut(event.dropped_entity) {
-            let textures = match child.species {
-                Species::Derp => Growable::derp(&texture_assets),
-                Species::Psycho => Growable::psycho(&texture_assets),
-                Species::Poser => Growable::poser(&texture_assets)
-            };
-
+            let growth_bar = commands.spawn((
+                WorldBarBundle::new(WorldBar::new(GROWTH_BAR_SIZE, vec![GROWTH_BAR_COLOR]).with_progress(0.0), Vec3::new(0.0, growth_bar_y(0, &tuning), 6.0)),
+                GrowthBar,
+            )).id();
             commands.entity(event.dropped_entity)
-                .insert(textures)
-                .remove::<Pulsing>();
+                .insert((Growable::default(), Tween::squash(Vec3::ONE, 0.25)))
+                .remove::<Pulsing>()
+                .add_child(growth_bar);
             transform.scale = Vec3::splat(1.0);
+            spawn_particles(&mut commands, transform.translation.truncate(), ParticleEmitter::spores());
+
+            growth_events.send(ChildGrowthEvent {
+                child: event.dropped_entity,
+                species: child.species,
+                step: GrowthStep::Planted,
+            });
         }
     }
 }
 
+/// Bar below a planted child, showing how far it is from growing up.
+#[derive(Component)]
+struct GrowthBar;
+
+/// Fills the growth bars, which are removed once the child grows up or leaves early.
+fn update_growth_bars(
+    mut commands: Commands,
+    tuning: Res<Tuning>,
+    growable_query: Query<&Growable>,
+    mut bar_query: Query<(Entity, &Parent, &mut WorldBar, &mut Transform), With<GrowthBar>>,
+) {
+    for (entity, parent, mut bar, mut transform) in &mut bar_query {
+        let Some(growable) = growable_query.get(parent.get()).ok().filter(|growable| growable.stage < GROW_STAGES - 1) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        bar.progress = growable.growth_fraction(tuning.grow_duration);
+        transform.translation.y = growth_bar_y(growable.stage, &tuning);
+    }
+}
+
+/// Center of the growth bar below the child, the spores are centered, but grown children stand on their origin.
+fn growth_bar_y(stage: usize, tuning: &Tuning) -> f32 {
+    let bottom = if stage == 0 { -tuning.child_hitbox_size / 2.0 } else { 0.0 };
+    bottom - GROWTH_BAR_GAP - GROWTH_BAR_SIZE.y / 2.0
+}
+
 #[derive(Component)]
 pub struct HypnoBehaviour {
     pub range: f32,
 }
 
+/// Child is stuck in a psycho's hypnosis. Points to the swirl shown above its head.
+#[derive(Component)]
+struct Hypnotized {
+    marker: Entity,
+}
+
+/// Swirl of a child freed from the hypnosis, despawned once it unwinds.
+#[derive(Component)]
+struct UnwindingMarker;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct HypnoGizmos {}
+
+/// Sent when a psycho child grows up and starts hypnotizing its surroundings.
+#[derive(Event, Debug)]
+pub struct HypnosisStartedEvent;
+
+/// Removes the hypnotic behaviour from the given child.
 #[derive(Event, Debug)]
 pub struct HypnoDespawnEvent {
     pub parent: Entity,
 }
 
 fn update_hypnotism(
-    mut victim_query: Query<(&mut Growable, &Transform)>,
-    hypno_query: Query<(&GlobalTransform, &HypnoBehaviour)>
+    mut commands: Commands,
+    animation_assets: Res<AnimationAssets>,
+    mut victim_query: Query<(Entity, &mut Growable, &Transform, Option<&Hypnotized>)>,
+    hypno_query: Query<(&GlobalTransform, &HypnoBehaviour, &Parent)>,
+    mut marker_query: Query<&mut Animation>,
 ) {
-    for (mut victim_growable, _) in victim_query.iter_mut() {
-        victim_growable.stopped_by_psycho = false;
+    let hypnotic_effect = animation_assets.get(Species::Psycho, AnimationState::Hypnotic, AnimationPart::Effect);
+
+    for (victim, mut victim_growable, victim_transform, hypnotized) in victim_query.iter_mut() {
+        // psychos don't hypnotize themselves
+        victim_growable.stopped_by_psycho = hypno_query.iter().any(|(transform, hypno_behaviour, hypnotist)| {
+            hypnotist.get() != victim
+                && transform.translation().truncate().distance(victim_transform.translation.truncate()) < hypno_behaviour.range
+        });
+
+        // grown ups are not growing anyway
+        let show_marker = victim_growable.stopped_by_psycho && victim_growable.stage < GROW_STAGES - 1;
+
+        match (show_marker, hypnotized) {
+            (true, None) => {
+                let mut marker_bundle = AnimationBundle::new(
+                    hypnotic_effect.clone(),
+                    0.15,
+                    HYPNOTIZED_MARKER_SCALE,
+                    1.0,
+                );
+                marker_bundle.sprite_sheet.transform.translation = HYPNOTIZED_MARKER_OFFSET.extend(1.0);
+                // winds up before spinning
+                marker_bundle.animation.play_once(
+                    hypnotic_effect.reversed(),
+                    Some(hypnotic_effect.clone()),
+                );
+
+                let marker = commands.spawn(marker_bundle).id();
+                commands.entity(victim)
+                    .add_child(marker)
+                    .insert(Hypnotized { marker });
+            },
+            (false, Some(hypnotized)) => {
+                match marker_query.get_mut(hypnotized.marker) {
+                    Ok(mut animation) => {
+                        animation.play_once(hypnotic_effect.reversed(), None);
+                        commands.entity(hypnotized.marker).insert(UnwindingMarker);
+                    },
+                    Err(_) => commands.entity(hypnotized.marker).despawn_recursive(),
+                }
+                commands.entity(victim).remove::<Hypnotized>();
+            },
+            _ => {},
+        }
     }
+}
 
-    for (transform, hypno_behaviour) in hypno_query.iter() {
-        for (mut victim_growable, victim_transform) in victim_query.iter_mut() {
-            if (transform.translation() - victim_transform.translation).length() < hypno_behaviour.range {
-                victim_growable.stopped_by_psycho = true;
-            }
+fn despawn_unwound_markers(
+    mut commands: Commands,
+    mut events: EventReader<AnimationFinished>,
+    marker_query: Query<(), With<UnwindingMarker>>,
+) {
+    for event in events.read() {
+        if marker_query.contains(event.entity) {
+            commands.entity(event.entity).despawn_recursive();
+        }
+    }
+}
+
+/// Swirls of the hypnotized children nearby throb whenever a psycho blinks.
+fn throb_hypnotized_markers(
+    mut commands: Commands,
+    mut events: EventReader<AnimationFrameEvent>,
+    eyes_query: Query<&Parent>,
+    children_query: Query<&Children>,
+    hypno_query: Query<(&GlobalTransform, &HypnoBehaviour)>,
+    victim_query: Query<(&Transform, &Hypnotized)>,
+    marker_query: Query<&Transform, Without<Tween>>,
+) {
+    for event in events.read() {
+        if event.marker != AnimationMarker::Blink { continue; }
+
+        // the same range as the aura, psychos asleep under the mask have none
+        let Ok(psycho) = eyes_query.get(event.entity) else { continue };
+        let Some((hypno_transform, hypno_behaviour)) = children_query.get(psycho.get()).ok()
+            .and_then(|children| children.iter().find_map(|child| hypno_query.get(*child).ok())) else { continue };
+        let center = hypno_transform.translation().truncate();
+
+        for (victim_transform, hypnotized) in &victim_query {
+            if victim_transform.translation.truncate().distance(center) >= hypno_behaviour.range { continue; }
+            let Ok(marker_transform) = marker_query.get(hypnotized.marker) else { continue };
+
+            commands.entity(hypnotized.marker).insert(Tween::squash(marker_transform.scale, HYPNOTIZED_MARKER_THROB));
         }
     }
 }
 
+fn configure_hypno_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<HypnoGizmos>();
+    config.line_width = 4.0;
+}
+
+fn draw_hypno_auras(
+    mut gizmos: Gizmos<HypnoGizmos>,
+    hypno_query: Query<(&GlobalTransform, &HypnoBehaviour)>,
+) {
+    for (transform, hypno_behaviour) in hypno_query.iter() {
+        gizmos.circle_2d(transform.translation().truncate(), hypno_behaviour.range, HYPNO_AURA_COLOR)
+            .segments(64);
+    }
+}
+
 fn read_hypno_despawn_events(
     mut commands: Commands,
     mut events: EventReader<HypnoDespawnEvent>,
-    query: Query<&Children, With<HypnoBehaviour>>
+    children_query: Query<&Children>,
+    hypno_query: Query<(), With<HypnoBehaviour>>
 ) {
     for event in events.read() {
-        for hypno_child in query.iter_descendants(event.parent) {
-            commands.entity(hypno_child).despawn();
+        for descendant in children_query.iter_descendants(event.parent) {
+            if hypno_query.contains(descendant) {
+                commands.entity(descendant).despawn_recursive();
+            }
         }
     }
 }
 
-fn add_hypnotic_behaviour(
+/// Psycho children start hypnotizing everything around once they grow up.
+struct PsychoBehaviour;
+
+impl SpeciesBehaviour for PsychoBehaviour {
+    fn on_maturity(&self, context: &mut BehaviourContext) {
+        add_hypnotic_behaviour(context.commands, context.child, context.animation_assets);
+
+        // waking up from the sleep mask adds the behaviour again, but that is no new hypnosis
+        context.commands.add(|world: &mut World| {
+            world.send_event(HypnosisStartedEvent);
+        });
+    }
+}
+
+pub fn add_hypnotic_behaviour(
     commands: &mut Commands,
     parent: Entity,
     animation_assets: &AnimationAssets,
 ) {
     let e = commands.spawn((
-        HypnoBehaviour { range: 500.0 },
-        AnimationBundle::new(animation_assets.hypnotic_effect.clone(), 0.15, 0.5, 1.0)
+        HypnoBehaviour { range: HYPNO_RANGE },
+        AnimationBundle::new(animation_assets.get(Species::Psycho, AnimationState::Hypnotic, AnimationPart::Effect), 0.15, 0.5, 1.0)
     )).id();
 
     commands.entity(parent).add_child(e);
@@ -1,11 +1,15 @@
 use bevy::{prelude::*, render::view::visibility};
 use enumset::EnumSet;
 
-use crate::hitbox::Hitbox;
+use crate::{hitbox::Hitbox, pulsing::Pulsing, tween::{Ease, Tween, TweenCompleted, TweenTarget}};
 use super::{collisions::EmitsCollisions, InLayers, Layer};
 
 const DRAGGABLE_SCALE: f32 = 1.2;
 const HOVER_Z: f32 = 5.0;
+/// How long the drag shadow flies back after a rejected drop.
+const SNAP_BACK_DURATION: f32 = 0.25;
+/// How far the dragged entity turns when shaking off a rejected drop, in radians.
+const REJECTED_SHAKE_ANGLE: f32 = 0.12;
 
 /// Dropping is blocked by entities with DropBlocker in layers that overlap with the draggable entity
 /// Example:
@@ -49,6 +53,18 @@ pub struct DropEvent {
     pub dropped_on_entity: Entity,
 }
 
+/// Sent when a drag ends over an invalid target and the dragged entity stays where it was.
+#[derive(Event, Debug)]
+pub struct DropRejected {
+    pub dropped_entity: Entity,
+    /// Where the drag shadow was let go.
+    pub position: Vec2,
+}
+
+/// Drag shadow flying back to its entity after a rejected drop, despawned once it arrives.
+#[derive(Component, Debug)]
+pub struct ReturningShadow;
+
 pub fn initiate_drag(
     mut commands: Commands,
     mouse_buttons: Res<ButtonInput<MouseButton>>,
@@ -144,6 +160,7 @@ pub fn update_drag(
 pub fn end_drag(
     mut commands: Commands,
     mut drop_events: ResMut<Events<DropEvent>>,
+    mut drop_rejected_events: EventWriter<DropRejected>,
     mouse_buttons: Res<ButtonInput<MouseButton>>,
     mut draggables: Query<(Entity, &Hitbox, &mut Draggable, &mut Transform, Option<&DropBlocker>, &InLayers), Without<DragShadow>>,
     non_draggable_hitboxes: Query<(&Hitbox, Option<&DropBlocker>, &InLayers, &Transform), Without<Draggable>>,
@@ -239,10 +256,50 @@ pub fn end_drag(
                     }
                 };
             } else {
-                commands.entity(drag_shadow_entity).despawn();
+                draggable.drag_shadow = None;
+                drop_rejected_events.send(DropRejected {
+                    dropped_entity: original_entity,
+                    position: drag_shadow_transform.translation.truncate(),
+                });
+                commands.entity(drag_shadow_entity)
+                    .remove::<(DragShadow, EmitsCollisions, Hitbox)>()
+                    .insert((
+                        ReturningShadow,
+                        Tween::new(TweenTarget::Translation {
+                            from: drag_shadow_transform.translation,
+                            to: transform.translation.truncate().extend(HOVER_Z),
+                        }, SNAP_BACK_DURATION, Ease::QuadInOut),
+                    ));
             }
         } else {
             commands.entity(drag_shadow_entity).despawn();
         }
     }
-}
\ No newline at end of file
+}
+
+pub fn despawn_returned_shadows(
+    mut commands: Commands,
+    mut events: EventReader<TweenCompleted>,
+    shadows: Query<(), With<ReturningShadow>>,
+) {
+    for event in events.read() {
+        if shadows.contains(event.entity) {
+            commands.entity(event.entity).despawn();
+        }
+    }
+}
+
+/// The entity shakes its head while its drag shadow is flying back.
+pub fn shake_rejected_drops(
+    mut commands: Commands,
+    mut events: EventReader<DropRejected>,
+    query: Query<(&Transform, Has<Tween>, Has<Pulsing>)>,
+) {
+    for event in events.read() {
+        let Ok((transform, tweening, pulsing)) = query.get(event.dropped_entity) else { continue };
+
+        // don't cut other animations short, only pulsing resumes on its own
+        if tweening && !pulsing { continue; }
+        commands.entity(event.dropped_entity).insert(Tween::shake(transform.rotation, REJECTED_SHAKE_ANGLE));
+    }
+}
@@ -14,7 +14,8 @@ impl Plugin for HitboxPlugin {
             .add_event::<CollisionEvent>()
             .add_systems(PreUpdate, emit_collision_events)
             .add_event::<DropEvent>()
-            .add_systems(Update, (initiate_drag, update_drag, end_drag)
+            .add_event::<DropRejected>()
+            .add_systems(Update, (initiate_drag, update_drag, end_drag, despawn_returned_shadows, shake_rejected_drops)
                 .run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))))
             .add_systems(OnExit(crate::PausedState::Unpaused), end_all_drags);
         if cfg!(debug_assertions) {
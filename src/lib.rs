@@ -21,10 +21,27 @@ mod cheats;
 mod score;
 mod pause;
 mod child_walking;
+mod child_clumsy;
 mod pulsing;
 mod music;
 mod difficulty;
 mod timer;
+mod profile;
+mod achievements;
+mod species;
+mod sleep_mask;
+mod parent_requests;
+mod parent_archetypes;
+mod tea_cup;
+mod waves;
+mod garden_events;
+mod day_night;
+mod particles;
+mod tween;
+mod loading_screen;
+mod tuning;
+mod localization;
+mod world_bar;
 
 use crate::animations::AnimationsPlugin;
 use crate::actions::ActionsPlugin;
@@ -46,10 +63,26 @@ use crate::score::ScorePlugin;
 pub use crate::pause::PausedState;
 use crate::pause::PausedPlugin;
 use crate::child_walking::ChildWalkingPlugin;
+use crate::child_clumsy::ChildClumsyPlugin;
 use crate::pulsing::PulsingPlugin;
 use crate::music::MusicPlugin;
 use crate::difficulty::DifficultyPlugin;
 use  crate::timer::TimerPlugin;
+use crate::profile::ProfilePlugin;
+use crate::achievements::AchievementsPlugin;
+use crate::species::SpeciesPlugin;
+use crate::sleep_mask::SleepMaskPlugin;
+use crate::parent_requests::ParentRequestsPlugin;
+use crate::tea_cup::TeaCupPlugin;
+use crate::waves::WavesPlugin;
+use crate::garden_events::GardenEventsPlugin;
+use crate::day_night::DayNightPlugin;
+use crate::particles::ParticlesPlugin;
+use crate::tween::TweenPlugin;
+use crate::loading_screen::LoadingScreenPlugin;
+use crate::tuning::TuningPlugin;
+use crate::localization::LocalizationPlugin;
+use crate::world_bar::WorldBarPlugin;
 
 pub const WINDOW_WIDTH: f32 = 1920.0;
 pub const WINDOW_HEIGHT: f32 = 1080.0;
@@ -110,11 +143,31 @@ impl Plugin for GamePlugin {
             ScorePlugin,
             PausedPlugin,
             ChildWalkingPlugin,
+            ChildClumsyPlugin,
             PulsingPlugin,
             MusicPlugin,
             DifficultyPlugin,
             TimerPlugin,
-        )); 
+            ProfilePlugin,
+            AchievementsPlugin,
+            SpeciesPlugin,
+            SleepMaskPlugin,
+            ParentRequestsPlugin,
+            TeaCupPlugin,
+        ))
+            .add_plugins((
+            WavesPlugin,
+            GardenEventsPlugin,
+            DayNightPlugin {
+                cycle_length: 4.0 * 60.0,
+            },
+            ParticlesPlugin,
+            TweenPlugin,
+            LoadingScreenPlugin,
+            TuningPlugin,
+            LocalizationPlugin,
+            WorldBarPlugin,
+        ));
 
         #[cfg(debug_assertions)]
         {
@@ -1,7 +1,8 @@
-use crate::GameState;
-use bevy::prelude::*;
+use crate::{animations::{AnimationFrames, AnimationKey, AnimationMarker, AnimationPart, AnimationState}, parents::Species, tuning::Tuning, waves::WaveTimeline, GameState};
+use bevy::{asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadDirectError}, prelude::*, utils::{BoxedFuture, HashMap}};
 use bevy_asset_loader::prelude::*;
 use bevy_kira_audio::AudioSource;
+use serde::Deserialize;
 
 pub struct LoadingPlugin;
 
@@ -15,8 +16,11 @@ impl Plugin for LoadingPlugin {
                 .continue_to_state(GameState::Menu)
                 .load_collection::<AudioAssets>()
                 .load_collection::<TextureAssets>()
-                
+                .load_collection::<ManifestAssets>()
+                .load_collection::<ConfigAssets>()
         )
+        .init_asset::<AnimationManifest>()
+        .init_asset_loader::<AnimationManifestLoader>()
         .init_resource::<AnimationAssets>()
         .init_resource::<TextureAssets>()
         .add_systems(OnExit(GameState::Loading), init_animation_resource);
@@ -36,10 +40,6 @@ pub struct AudioAssets {
 
 #[derive(AssetCollection, Resource, Default)]
 pub struct TextureAssets {
-    #[asset(path = "textures/nothing.png")]
-    pub nothing: Handle<Image>,
-    
-
     #[asset(path = "textures/garden/garden.png")]
     pub garden_background: Handle<Image>,
     // worms
@@ -64,6 +64,10 @@ pub struct TextureAssets {
     pub water_leaves_empty: Handle<Image>,
     #[asset(path = "textures/garden/bubble_water.png")]
     pub bubble_water: Handle<Image>,
+    #[asset(path = "textures/sleep_mask.png")]
+    pub sleep_mask: Handle<Image>,
+    #[asset(path = "textures/tea_cup.png")]
+    pub tea_cup: Handle<Image>,
 
     #[asset(path = "textures/mushroom_default_baby_icon.png")]
     pub mushroom_default_baby_icon: Handle<Image>,
@@ -87,425 +91,165 @@ pub struct TextureAssets {
     pub placeholder_water_source: Handle<Image>,
     #[asset(path = "textures/thirst_bubble.png")]
     pub placeholder_thirst_bubble: Handle<Image>,
+}
 
-    #[asset(path = "textures/mushrooms/derp/derp_spores.png")]
-    pub derp_spores: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/derp_baby_body.png")]
-    pub derp_baby_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/derp_child_body.png")]
-    pub derp_child_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/derp_teenager_body.png")]
-    pub derp_teenager_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/derp_parent_body.png")]
-    pub derp_parent_body: Handle<Image>,
-
-    #[asset(path = "textures/mushrooms/derp/derp_baby_eyes.png")]
-    pub derp_baby_eyes: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/derp_child_eyes.png")]
-    pub derp_child_eyes: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/derp_teenager_eyes.png")]
-    pub derp_teenager_eyes: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/derp_parent_eyes.png")]
-    pub derp_parent_eyes: Handle<Image>,
-
-    #[asset(path = "textures/mushrooms/psycho/psycho_spores.png")]
-    pub psycho_spores: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/psycho_baby_body.png")]
-    pub psycho_baby_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/psycho_child_body.png")]
-    pub psycho_child_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/psycho_teenager_body.png")]
-    pub psycho_teenager_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/psycho_parent_body.png")]
-    pub psycho_parent_body: Handle<Image>,
-
-    #[asset(path = "textures/mushrooms/psycho/psycho_baby_eyes.png")]
-    pub psycho_baby_eyes: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/psycho_child_eyes.png")]
-    pub psycho_child_eyes: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/psycho_teenager_eyes.png")]
-    pub psycho_teenager_eyes: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/psycho_parent_eyes.png")]
-    pub psycho_parent_eyes: Handle<Image>,
-
-    #[asset(path = "textures/mushrooms/poser/poser_spores.png")]
-    pub poser_spores: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/poser_baby_body.png")]
-    pub poser_baby_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/poser_child_body.png")]
-    pub poser_child_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/poser_teenager_body.png")]
-    pub poser_teenager_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/poser_parent_body.png")]
-    pub poser_parent_body: Handle<Image>,
-
-    #[asset(path = "textures/mushrooms/poser/poser_baby_eyes.png")]
-    pub poser_baby_eyes: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/poser_child_eyes.png")]
-    pub poser_child_eyes: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/poser_teenager_eyes.png")]
-    pub poser_teenager_eyes: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/poser_parent_eyes.png")]
-    pub poser_parent_eyes: Handle<Image>,
-
-    // Psycho child hypnotic
-    #[asset(path = "textures/mushrooms/psycho/hypno_child/psycho_child_hypnotic_body.png")]
-    pub psycho_child_hypnotic_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/hypno_child/psycho_child_hypnotic_eyes_1.png")]
-    pub psycho_child_hypnotic_eyes_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/hypno_child/psycho_child_hypnotic_eyes_2.png")]
-    pub psycho_child_hypnotic_eyes_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/hypno_child/psycho_child_hypnotic_eyes_3.png")]
-    pub psycho_child_hypnotic_eyes_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/hypno_child/psycho_child_hypnotic_eyes_4.png")]
-    pub psycho_child_hypnotic_eyes_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/hypno_child/psycho_child_hypnotic_eyes_5.png")]
-    pub psycho_child_hypnotic_eyes_5: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/hypno_child/psycho_child_hypnotic_eyes_6.png")]
-    pub psycho_child_hypnotic_eyes_6: Handle<Image>,
-
-    // Hypnotic Effect
-    #[asset(path = "textures/hypnotic_effect/hypnotic_effect_1.png")]
-    pub hypnotic_effect_1: Handle<Image>,
-    #[asset(path = "textures/hypnotic_effect/hypnotic_effect_2.png")]
-    pub hypnotic_effect_2: Handle<Image>,
-    #[asset(path = "textures/hypnotic_effect/hypnotic_effect_3.png")]
-    pub hypnotic_effect_3: Handle<Image>,
-    #[asset(path = "textures/hypnotic_effect/hypnotic_effect_4.png")]
-    pub hypnotic_effect_4: Handle<Image>,
-    #[asset(path = "textures/hypnotic_effect/hypnotic_effect_5.png")]
-    pub hypnotic_effect_5: Handle<Image>,
-    #[asset(path = "textures/hypnotic_effect/hypnotic_effect_6.png")]
-    pub hypnotic_effect_6: Handle<Image>,
-
-    // Parent Walking Animations
-    //====================================
-
-    // Derp
-    #[asset(path = "textures/mushrooms/derp/walking/derp_parent_walking_body_1.png")]
-    pub derp_parent_walking_body_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/walking/derp_parent_walking_body_2.png")]
-    pub derp_parent_walking_body_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/walking/derp_parent_walking_body_3.png")]
-    pub derp_parent_walking_body_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/walking/derp_parent_walking_body_4.png")]
-    pub derp_parent_walking_body_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/walking/derp_parent_walking_body_5.png")]
-    pub derp_parent_walking_body_5: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/walking/derp_parent_walking_body_6.png")]
-    pub derp_parent_walking_body_6: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/walking/derp_parent_walking_eyes.png")]
-    pub derp_parent_walking_eyes: Handle<Image>,
+#[derive(AssetCollection, Resource)]
+pub struct ManifestAssets {
+    #[asset(path = "animations.manifest.ron")]
+    pub animations: Handle<AnimationManifest>,
+}
 
-    // Psycho
-    #[asset(path = "textures/mushrooms/psycho/walking/psycho_parent_walking_body_1.png")]
-    pub psycho_parent_walking_body_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/walking/psycho_parent_walking_body_2.png")]
-    pub psycho_parent_walking_body_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/walking/psycho_parent_walking_body_3.png")]
-    pub psycho_parent_walking_body_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/walking/psycho_parent_walking_body_4.png")]
-    pub psycho_parent_walking_body_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/walking/psycho_parent_walking_body_5.png")]
-    pub psycho_parent_walking_body_5: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/walking/psycho_parent_walking_eyes.png")]
-    pub psycho_parent_walking_eyes: Handle<Image>,
+/// Data files describing the gameplay.
+#[derive(AssetCollection, Resource)]
+pub struct ConfigAssets {
+    #[asset(path = "config/gameplay.tuning.ron")]
+    pub tuning: Handle<Tuning>,
+    #[asset(path = "config/endless.timeline.ron")]
+    pub endless_timeline: Handle<WaveTimeline>,
+    #[asset(path = "config/workday.timeline.ron")]
+    pub workday_timeline: Handle<WaveTimeline>,
+}
 
-    // Poser
-    #[asset(path = "textures/mushrooms/poser/walking/poser_parent_walking_body_1.png")]
-    pub poser_parent_walking_body_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/walking/poser_parent_walking_body_2.png")]
-    pub poser_parent_walking_body_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/walking/poser_parent_walking_body_3.png")]
-    pub poser_parent_walking_body_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/walking/poser_parent_walking_body_4.png")]
-    pub poser_parent_walking_body_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/walking/poser_parent_walking_body_5.png")]
-    pub poser_parent_walking_body_5: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/walking/poser_parent_walking_body_6.png")]
-    pub poser_parent_walking_body_6: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/walking/poser_parent_walking_eyes.png")]
-    pub poser_parent_walking_eyes: Handle<Image>,
+/// Animation as written in the manifest file.
+#[derive(Deserialize)]
+struct ManifestEntry {
+    species: Species,
+    state: AnimationState,
+    part: AnimationPart,
+    /// Paths of the frame images, in the order they are played.
+    frames: Vec<String>,
+    #[serde(default)]
+    markers: Vec<(usize, AnimationMarker)>,
+}
 
-    // Parent Patient Animations
-    //====================================
+#[derive(Deserialize)]
+struct ManifestFile {
+    animations: Vec<ManifestEntry>,
+}
 
-    // Derp
-    #[asset(path = "textures/mushrooms/derp/patient/derp_parent_patient_body.png")]
-    pub derp_parent_patient_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/patient/derp_parent_patient_eyes_1.png")]
-    pub derp_parent_patient_eyes_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/patient/derp_parent_patient_eyes_2.png")]
-    pub derp_parent_patient_eyes_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/patient/derp_parent_patient_eyes_3.png")]
-    pub derp_parent_patient_eyes_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/patient/derp_parent_patient_eyes_4.png")]
-    pub derp_parent_patient_eyes_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/patient/derp_parent_patient_eyes_5.png")]
-    pub derp_parent_patient_eyes_5: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/patient/derp_parent_patient_eyes_6.png")]
-    pub derp_parent_patient_eyes_6: Handle<Image>,
+/// Animation of the manifest with its frames loaded.
+pub struct ManifestAnimation {
+    pub key: AnimationKey,
+    pub frames: Vec<Handle<Image>>,
+    pub markers: Vec<(usize, AnimationMarker)>,
+}
 
-    // Psycho
-    #[asset(path = "textures/mushrooms/psycho/patient/psycho_parent_patient_body.png")]
-    pub psycho_parent_patient_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/patient/psycho_parent_patient_eyes_1.png")]
-    pub psycho_parent_patient_eyes_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/patient/psycho_parent_patient_eyes_2.png")]
-    pub psycho_parent_patient_eyes_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/patient/psycho_parent_patient_eyes_3.png")]
-    pub psycho_parent_patient_eyes_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/patient/psycho_parent_patient_eyes_4.png")]
-    pub psycho_parent_patient_eyes_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/patient/psycho_parent_patient_eyes_5.png")]
-    pub psycho_parent_patient_eyes_5: Handle<Image>,
+/// List of all the animations, loaded from `*.manifest.ron` files.
+/// Frames are loaded together with the manifest, so adding a frame only takes a line in the manifest.
+#[derive(Asset, TypePath)]
+pub struct AnimationManifest {
+    pub animations: Vec<ManifestAnimation>,
+}
 
-    // Poser
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_body_1.png")]
-    pub poser_parent_patient_body_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_body_2.png")]
-    pub poser_parent_patient_body_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_body_3.png")]
-    pub poser_parent_patient_body_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_body_4.png")]
-    pub poser_parent_patient_body_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_body_5.png")]
-    pub poser_parent_patient_body_5: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_body_6.png")]
-    pub poser_parent_patient_body_6: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_eyes_1.png")]
-    pub poser_parent_patient_eyes_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_eyes_2.png")]
-    pub poser_parent_patient_eyes_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_eyes_3.png")]
-    pub poser_parent_patient_eyes_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_eyes_4.png")]
-    pub poser_parent_patient_eyes_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/patient/poser_parent_patient_eyes_5.png")]
-    pub poser_parent_patient_eyes_5: Handle<Image>,
+#[derive(Debug)]
+pub enum AnimationManifestError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Frame(LoadDirectError),
+    NotAnImage(String),
+}
 
-    // Parent Nervous Animations
-    //====================================
-    
-    // Derp
-    #[asset(path = "textures/mushrooms/derp/nervous/derp_parent_nervous_body_1.png")]
-    pub derp_parent_nervous_body_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/nervous/derp_parent_nervous_body_2.png")]
-    pub derp_parent_nervous_body_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/nervous/derp_parent_nervous_body_3.png")]
-    pub derp_parent_nervous_body_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/nervous/derp_parent_nervous_body_4.png")]
-    pub derp_parent_nervous_body_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/derp/nervous/derp_parent_nervous_eyes.png")]
-    pub derp_parent_nervous_eyes: Handle<Image>,
+impl std::fmt::Display for AnimationManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnimationManifestError::Io(err) => write!(f, "could not read the manifest: {}", err),
+            AnimationManifestError::Ron(err) => write!(f, "could not parse the manifest: {}", err),
+            AnimationManifestError::Frame(err) => write!(f, "could not load a frame: {}", err),
+            AnimationManifestError::NotAnImage(path) => write!(f, "frame {} is not an image", path),
+        }
+    }
+}
 
-    // Psycho
-    #[asset(path = "textures/mushrooms/psycho/nervous/psycho_parent_nervous_body.png")]
-    pub psycho_parent_nervous_body: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/nervous/psycho_parent_nervous_eyes_1.png")]
-    pub psycho_parent_nervous_eyes_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/nervous/psycho_parent_nervous_eyes_2.png")]
-    pub psycho_parent_nervous_eyes_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/nervous/psycho_parent_nervous_eyes_3.png")]
-    pub psycho_parent_nervous_eyes_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/nervous/psycho_parent_nervous_eyes_4.png")]
-    pub psycho_parent_nervous_eyes_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/psycho/nervous/psycho_parent_nervous_eyes_5.png")]
-    pub psycho_parent_nervous_eyes_5: Handle<Image>,
+impl std::error::Error for AnimationManifestError {}
+
+#[derive(Default)]
+struct AnimationManifestLoader;
+
+impl AssetLoader for AnimationManifestLoader {
+    type Asset = AnimationManifest;
+    type Settings = ();
+    type Error = AnimationManifestError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<AnimationManifest, AnimationManifestError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await.map_err(AnimationManifestError::Io)?;
+            let file: ManifestFile = ron::de::from_bytes(&bytes).map_err(AnimationManifestError::Ron)?;
+
+            let mut animations = Vec::new();
+            for entry in file.animations {
+                let mut frames = Vec::new();
+                // frames are loaded right away, so the manifest counts as loaded only once they are
+                for path in entry.frames {
+                    let loaded = load_context.load_direct(&path).await.map_err(AnimationManifestError::Frame)?;
+                    let image = loaded.take::<Image>().ok_or_else(|| AnimationManifestError::NotAnImage(path.clone()))?;
+                    frames.push(load_context.add_labeled_asset(path, image));
+                }
+
+                animations.push(ManifestAnimation {
+                    key: AnimationKey { species: entry.species, state: entry.state, part: entry.part },
+                    frames,
+                    markers: entry.markers,
+                });
+            }
+
+            Ok(AnimationManifest { animations })
+        })
+    }
 
-    // Poser
-    #[asset(path = "textures/mushrooms/poser/nervous/poser_parent_nervous_body_1.png")]
-    pub poser_parent_nervous_body_1: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/nervous/poser_parent_nervous_body_2.png")]
-    pub poser_parent_nervous_body_2: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/nervous/poser_parent_nervous_body_3.png")]
-    pub poser_parent_nervous_body_3: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/nervous/poser_parent_nervous_body_4.png")]
-    pub poser_parent_nervous_body_4: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/nervous/poser_parent_nervous_body_5.png")]
-    pub poser_parent_nervous_body_5: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/nervous/poser_parent_nervous_body_6.png")]
-    pub poser_parent_nervous_body_6: Handle<Image>,
-    #[asset(path = "textures/mushrooms/poser/nervous/poser_parent_nervous_eyes.png")]
-    pub poser_parent_nervous_eyes: Handle<Image>,
+    fn extensions(&self) -> &[&str] {
+        &["manifest.ron"]
+    }
 }
 
+/// Animations of the manifest, packed into texture atlases.
 #[derive(Resource, Default)]
 pub struct AnimationAssets {
-    // Psycho Child Animation
-    pub psycho_child_hypnotic_body: Vec<Handle<Image>>,
-    pub psycho_child_hypnotic_eyes: Vec<Handle<Image>>,
-
-    // Hypnotic Effect
-    pub hypnotic_effect: Vec<Handle<Image>>,
-
-    // Parent Walking Animations
-    pub derp_parent_walking_body: Vec<Handle<Image>>,
-    pub psycho_parent_walking_body: Vec<Handle<Image>>,
-    pub poser_parent_walking_body: Vec<Handle<Image>>,
-
-    pub derp_parent_walking_eyes: Vec<Handle<Image>>,
-    pub psycho_parent_walking_eyes: Vec<Handle<Image>>,
-    pub poser_parent_walking_eyes: Vec<Handle<Image>>,
-
-    // Parent Patient Animations
-    pub derp_parent_patient_body: Vec<Handle<Image>>,
-    pub psycho_parent_patient_body: Vec<Handle<Image>>,
-    pub poser_parent_patient_body: Vec<Handle<Image>>,
-
-    pub derp_parent_patient_eyes: Vec<Handle<Image>>,
-    pub psycho_parent_patient_eyes: Vec<Handle<Image>>,
-    pub poser_parent_patient_eyes: Vec<Handle<Image>>,
-
-    // Parent Nervous Animations
-    pub derp_parent_nervous_body: Vec<Handle<Image>>,
-    pub psycho_parent_nervous_body: Vec<Handle<Image>>,
-    pub poser_parent_nervous_body: Vec<Handle<Image>>,
+    animations: HashMap<AnimationKey, AnimationFrames>,
+}
 
-    pub derp_parent_nervous_eyes: Vec<Handle<Image>>,
-    pub psycho_parent_nervous_eyes: Vec<Handle<Image>>,
-    pub poser_parent_nervous_eyes: Vec<Handle<Image>>,
+impl AnimationAssets {
+    pub fn get(&self, species: Species, state: AnimationState, part: AnimationPart) -> AnimationFrames {
+        let key = AnimationKey { species, state, part };
+        self.animations.get(&key).cloned().unwrap_or_else(|| {
+            warn!("animation {:?} is missing from the manifest", key);
+            AnimationFrames::default()
+        })
+    }
 }
 
+/// Packs the frames of each animation into its own texture atlas, still images are used as they are.
+/// The manifest is dropped afterwards, so that the packed frames don't stay in memory twice.
 pub fn init_animation_resource(
+    mut commands: Commands,
     mut animation_assets: ResMut<AnimationAssets>,
-    texture_assets: Res<TextureAssets>
+    manifest_assets: Res<ManifestAssets>,
+    manifests: Res<Assets<AnimationManifest>>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    // Psycho Hypno Child
-    let vec = &mut animation_assets.psycho_child_hypnotic_eyes;
-    vec.push(texture_assets.psycho_child_hypnotic_eyes_1.clone());
-    vec.push(texture_assets.psycho_child_hypnotic_eyes_2.clone());
-    vec.push(texture_assets.psycho_child_hypnotic_eyes_3.clone());
-    vec.push(texture_assets.psycho_child_hypnotic_eyes_4.clone());
-    vec.push(texture_assets.psycho_child_hypnotic_eyes_5.clone());
-    animation_assets.psycho_child_hypnotic_body.push(
-        texture_assets.psycho_child_hypnotic_body.clone()
-    );
-
-    // Hypnotic Effect
-    let vec = &mut animation_assets.hypnotic_effect;
-    vec.push(texture_assets.hypnotic_effect_1.clone());
-    vec.push(texture_assets.hypnotic_effect_2.clone());
-    vec.push(texture_assets.hypnotic_effect_3.clone());
-    vec.push(texture_assets.hypnotic_effect_4.clone());
-    vec.push(texture_assets.hypnotic_effect_5.clone());
-    vec.push(texture_assets.hypnotic_effect_6.clone());
-
-    // Parent Walking Animations
-    //==================================
-
-    // Derp
-    let vec = &mut animation_assets.derp_parent_walking_body;
-    vec.push(texture_assets.derp_parent_walking_body_1.clone());
-    vec.push(texture_assets.derp_parent_walking_body_2.clone());
-    vec.push(texture_assets.derp_parent_walking_body_3.clone());
-    vec.push(texture_assets.derp_parent_walking_body_4.clone());
-    vec.push(texture_assets.derp_parent_walking_body_5.clone());
-    vec.push(texture_assets.derp_parent_walking_body_6.clone());
-    animation_assets.derp_parent_walking_eyes.push(
-        texture_assets.derp_parent_walking_eyes.clone()
-    );
-
-    // Psycho
-    let vec = &mut animation_assets.psycho_parent_walking_body;
-    vec.push(texture_assets.psycho_parent_walking_body_1.clone());
-    vec.push(texture_assets.psycho_parent_walking_body_2.clone());
-    vec.push(texture_assets.psycho_parent_walking_body_3.clone());
-    vec.push(texture_assets.psycho_parent_walking_body_4.clone());
-    vec.push(texture_assets.psycho_parent_walking_body_5.clone());
-    animation_assets.psycho_parent_walking_eyes.push(
-        texture_assets.psycho_parent_walking_eyes.clone()
-    );
-
-    // Poser
-    let vec = &mut animation_assets.poser_parent_walking_body;
-    vec.push(texture_assets.poser_parent_walking_body_1.clone());
-    vec.push(texture_assets.poser_parent_walking_body_2.clone());
-    vec.push(texture_assets.poser_parent_walking_body_3.clone());
-    vec.push(texture_assets.poser_parent_walking_body_4.clone());
-    vec.push(texture_assets.poser_parent_walking_body_5.clone());
-    vec.push(texture_assets.poser_parent_walking_body_6.clone());
-    animation_assets.poser_parent_walking_eyes.push(
-        texture_assets.poser_parent_walking_eyes.clone()
-    );
-
-    // Parent Patient Animations
-    //==================================
-
-    // Derp
-    let vec = &mut animation_assets.derp_parent_patient_eyes;
-    vec.push(texture_assets.derp_parent_patient_eyes_1.clone());
-    vec.push(texture_assets.derp_parent_patient_eyes_2.clone());
-    vec.push(texture_assets.derp_parent_patient_eyes_3.clone());
-    vec.push(texture_assets.derp_parent_patient_eyes_4.clone());
-    vec.push(texture_assets.derp_parent_patient_eyes_5.clone());
-    vec.push(texture_assets.derp_parent_patient_eyes_6.clone());
-    animation_assets.derp_parent_patient_body.push(
-        texture_assets.derp_parent_patient_body.clone()
-    );
-
-    // Psycho
-    let vec = &mut animation_assets.psycho_parent_patient_eyes;
-    vec.push(texture_assets.psycho_parent_patient_eyes_1.clone());
-    vec.push(texture_assets.psycho_parent_patient_eyes_2.clone());
-    vec.push(texture_assets.psycho_parent_patient_eyes_3.clone());
-    vec.push(texture_assets.psycho_parent_patient_eyes_4.clone());
-    vec.push(texture_assets.psycho_parent_patient_eyes_5.clone());
-    animation_assets.psycho_parent_patient_body.push(
-        texture_assets.psycho_parent_patient_body.clone()
-    );
-    
-    // Poser
-    let vec = &mut animation_assets.poser_parent_patient_body;
-    vec.push(texture_assets.poser_parent_patient_body_1.clone());
-    vec.push(texture_assets.poser_parent_patient_body_2.clone());
-    vec.push(texture_assets.poser_parent_patient_body_3.clone());
-    vec.push(texture_assets.poser_parent_patient_body_4.clone());
-    vec.push(texture_assets.poser_parent_patient_body_5.clone());
-    vec.push(texture_assets.poser_parent_patient_body_6.clone());
-
-    let vec = &mut animation_assets.poser_parent_patient_eyes;
-    vec.push(texture_assets.poser_parent_patient_eyes_1.clone());
-    vec.push(texture_assets.poser_parent_patient_eyes_2.clone());
-    vec.push(texture_assets.poser_parent_patient_eyes_3.clone());
-    vec.push(texture_assets.poser_parent_patient_eyes_4.clone());
-    vec.push(texture_assets.poser_parent_patient_eyes_5.clone());
-    
-    // Parent Patient Animations
-    //==================================
-
-    // Derp
-    let vec = &mut animation_assets.derp_parent_nervous_body;
-    vec.push(texture_assets.derp_parent_nervous_body_1.clone());
-    vec.push(texture_assets.derp_parent_nervous_body_2.clone());
-    vec.push(texture_assets.derp_parent_nervous_body_3.clone());
-    vec.push(texture_assets.derp_parent_nervous_body_4.clone());
-    animation_assets.derp_parent_nervous_eyes.push(
-        texture_assets.derp_parent_nervous_eyes.clone()
-    );
-
-    // Psycho
-    let vec = &mut animation_assets.psycho_parent_nervous_eyes;
-    vec.push(texture_assets.psycho_parent_nervous_eyes_1.clone());
-    vec.push(texture_assets.psycho_parent_nervous_eyes_2.clone());
-    vec.push(texture_assets.psycho_parent_nervous_eyes_3.clone());
-    vec.push(texture_assets.psycho_parent_nervous_eyes_4.clone());
-    vec.push(texture_assets.psycho_parent_nervous_eyes_5.clone());
-    animation_assets.psycho_parent_nervous_body.push(
-        texture_assets.psycho_parent_nervous_body.clone()
-    );
-    
-    // Poser
-    let vec = &mut animation_assets.poser_parent_nervous_body;
-    vec.push(texture_assets.poser_parent_nervous_body_1.clone());
-    vec.push(texture_assets.poser_parent_nervous_body_2.clone());
-    vec.push(texture_assets.poser_parent_nervous_body_3.clone());
-    vec.push(texture_assets.poser_parent_nervous_body_4.clone());
-    vec.push(texture_assets.poser_parent_nervous_body_5.clone());
-    vec.push(texture_assets.poser_parent_nervous_body_6.clone());
+    let Some(manifest) = manifests.get(&manifest_assets.animations) else {
+        error!("animation manifest is not loaded");
+        return;
+    };
+
+    for animation in &manifest.animations {
+        let mut frames = match animation.frames.as_slice() {
+            [frame] => AnimationFrames::single(frame.clone()),
+            frames => AnimationFrames::pack(frames, &mut images, &mut layouts),
+        };
+        for &(frame, marker) in &animation.markers {
+            frames = frames.with_marker(frame, marker);
+        }
+
+        animation_assets.animations.insert(animation.key, frames);
+    }
 
-    animation_assets.poser_parent_nervous_eyes.push(
-        texture_assets.poser_parent_nervous_eyes.clone()
-    );
+    // still images are kept alive by their animations, the rest only by the manifest
+    commands.remove_resource::<ManifestAssets>();
 }
-
@@ -0,0 +1,242 @@
+use bevy::{asset::{LoadState, UntypedAssetLoadFailedEvent}, prelude::*};
+use bevy_asset_loader::prelude::*;
+use bevy_progressbar::{ProgressBar, ProgressBarBundle, ProgressBarMaterial};
+
+use crate::{loading::{AudioAssets, ConfigAssets, ManifestAssets, TextureAssets}, localization::Localization, GameState};
+
+const BAR_WIDTH: f32 = 600.0;
+const BAR_HEIGHT: f32 = 30.0;
+const BAR_COLOR: Color = Color::rgb(0.55, 0.8, 0.4);
+const TEXT_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+const ERROR_COLOR: Color = Color::rgb(1.0, 0.45, 0.4);
+/// Seconds each tip stays on the screen.
+const TIP_DURATION: f32 = 4.0;
+
+/// Keys of the tips in the string tables.
+const TIPS: [&str; 6] = [
+    "loading.tip.needs",
+    "loading.tip.delivery",
+    "loading.tip.psycho",
+    "loading.tip.tea",
+    "loading.tip.day_night",
+    "loading.tip.rush",
+];
+
+pub struct LoadingScreenPlugin;
+
+/// Handles of all the asset collections, to tell how far the loading got.
+#[derive(Resource, Default)]
+struct LoadingProgress {
+    handles: Vec<UntypedHandle>,
+}
+
+#[derive(Resource)]
+struct TipRotation {
+    timer: Timer,
+    index: usize,
+}
+
+#[derive(Component)]
+struct LoadingScreen;
+
+#[derive(Component)]
+struct LoadingBar;
+
+#[derive(Component)]
+struct LoadingCountText;
+
+#[derive(Component)]
+struct TipText;
+
+impl Plugin for LoadingScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<LoadingProgress>()
+            .insert_resource(TipRotation {
+                timer: Timer::from_seconds(TIP_DURATION, TimerMode::Repeating),
+                index: 0,
+            })
+            .add_systems(OnEnter(GameState::Loading), (track_asset_collections, setup_loading_screen))
+            .add_systems(Update, (
+                update_loading_progress,
+                rotate_tips,
+                show_load_errors,
+            ).run_if(in_state(GameState::Loading)))
+            .add_systems(OnExit(GameState::Loading), cleanup_loading_screen);
+    }
+}
+
+/// Asks for the handles of the collections again, the asset server hands out the ones already loading.
+fn track_asset_collections(world: &mut World) {
+    let mut handles = AudioAssets::load(world);
+    handles.extend(TextureAssets::load(world));
+    handles.extend(ManifestAssets::load(world));
+    handles.extend(ConfigAssets::load(world));
+
+    world.insert_resource(LoadingProgress { handles });
+}
+
+fn setup_loading_screen(
+    mut commands: Commands,
+    mut bar_materials: ResMut<Assets<ProgressBarMaterial>>,
+    localization: Res<Localization>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                ..default()
+            },
+            LoadingScreen,
+        ))
+        .with_children(|children| {
+            children.spawn(TextBundle::from_section(
+                localization.text("loading.title"),
+                TextStyle {
+                    font_size: 60.0,
+                    color: TEXT_COLOR,
+                    ..default()
+                },
+            ));
+
+            children.spawn((
+                ProgressBarBundle::new(
+                    Style {
+                        width: Val::Px(BAR_WIDTH),
+                        height: Val::Px(BAR_HEIGHT),
+                        ..default()
+                    },
+                    ProgressBar::new(vec![(1, BAR_COLOR)]),
+                    &mut bar_materials,
+                ),
+                LoadingBar,
+            ));
+
+            children.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 30.0,
+                        color: TEXT_COLOR,
+                        ..default()
+                    },
+                ),
+                LoadingCountText,
+            ));
+
+            children.spawn((
+                TextBundle::from_section(
+                    localization.text(TIPS[0]),
+                    TextStyle {
+                        font_size: 30.0,
+                        color: TEXT_COLOR,
+                        ..default()
+                    },
+                ),
+                TipText,
+            ));
+        });
+}
+
+fn update_loading_progress(
+    asset_server: Res<AssetServer>,
+    progress: Res<LoadingProgress>,
+    mut bar_query: Query<&mut ProgressBar, With<LoadingBar>>,
+    mut text_query: Query<&mut Text, With<LoadingCountText>>,
+    localization: Res<Localization>,
+) {
+    let total = progress.handles.len();
+    let done = progress.handles.iter()
+        .filter(|handle| asset_server.get_load_state(handle.id()) == Some(LoadState::Loaded))
+        .count();
+
+    for mut bar in &mut bar_query {
+        bar.set_progress(if total > 0 { done as f32 / total as f32 } else { 0.0 });
+    }
+
+    for mut text in &mut text_query {
+        text.sections[0].value = localization.format("loading.progress", &[("done", &done), ("total", &total)]);
+    }
+}
+
+fn rotate_tips(
+    time: Res<Time>,
+    mut rotation: ResMut<TipRotation>,
+    mut query: Query<&mut Text, With<TipText>>,
+    localization: Res<Localization>,
+) {
+    rotation.timer.tick(time.delta());
+    if !rotation.timer.just_finished() { return; }
+
+    rotation.index = (rotation.index + 1) % TIPS.len();
+    for mut text in &mut query {
+        text.sections[0].value = localization.text(TIPS[rotation.index]);
+    }
+}
+
+/// Loading never finishes once an asset failed, so the loading screen is replaced by the error.
+fn show_load_errors(
+    mut commands: Commands,
+    mut events: EventReader<UntypedAssetLoadFailedEvent>,
+    screen_query: Query<Entity, With<LoadingScreen>>,
+    localization: Res<Localization>,
+) {
+    let Some(event) = events.read().last() else { return };
+    error!("failed to load {}: {}", event.path, event.error);
+
+    for entity in &screen_query {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
+                    padding: UiRect::all(Val::Px(40.0)),
+                    ..default()
+                },
+                background_color: Color::rgb(0.1, 0.05, 0.05).into(),
+                ..default()
+            },
+            LoadingScreen,
+        ))
+        .with_children(|children| {
+            children.spawn(TextBundle::from_section(
+                localization.format("loading.error", &[("path", &event.path)]),
+                TextStyle {
+                    font_size: 50.0,
+                    color: ERROR_COLOR,
+                    ..default()
+                },
+            ));
+
+            children.spawn(TextBundle::from_section(
+                event.error.to_string(),
+                TextStyle {
+                    font_size: 24.0,
+                    color: TEXT_COLOR,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn cleanup_loading_screen(mut commands: Commands, query: Query<Entity, With<LoadingScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
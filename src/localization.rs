@@ -0,0 +1,252 @@
+use std::{collections::{HashMap, HashSet}, fmt::Display, sync::Mutex};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{profile::{save_profile, Profile}, GameState};
+
+/// The default font covers only ASCII, this one has the Czech letters too.
+/// Embedded like the default font, so that the loading screen can use it.
+const FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSansMono.ttf");
+
+pub struct LocalizationPlugin;
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Language {
+    #[default]
+    English,
+    Czech,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Czech];
+
+    /// Strings are embedded, so that they are there before any asset is loaded.
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../assets/locale/en.ron"),
+            Language::Czech => include_str!("../assets/locale/cs.ron"),
+        }
+    }
+
+    fn plural_category(&self, count: u32) -> PluralCategory {
+        match self {
+            Language::English => match count {
+                1 => PluralCategory::One,
+                _ => PluralCategory::Other,
+            },
+            Language::Czech => match count {
+                1 => PluralCategory::One,
+                2..=4 => PluralCategory::Few,
+                _ => PluralCategory::Other,
+            },
+        }
+    }
+
+    fn next(&self) -> Language {
+        let index = Language::ALL.iter().position(|language| language == self).unwrap_or(0);
+        Language::ALL[(index + 1) % Language::ALL.len()]
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+enum PluralCategory {
+    One,
+    /// Used by Czech for 2 to 4.
+    Few,
+    Other,
+}
+
+/// Forms of a pluralized string, languages without the `few` form leave it out.
+#[derive(Deserialize, Debug)]
+struct PluralForms {
+    one: String,
+    #[serde(default)]
+    few: Option<String>,
+    other: String,
+}
+
+impl PluralForms {
+    fn get(&self, category: PluralCategory) -> &str {
+        match category {
+            PluralCategory::One => &self.one,
+            PluralCategory::Few => self.few.as_deref().unwrap_or(&self.other),
+            PluralCategory::Other => &self.other,
+        }
+    }
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+struct StringTable {
+    strings: HashMap<String, String>,
+    plurals: HashMap<String, PluralForms>,
+}
+
+/// Strings of all the languages and the one currently in use.
+/// Missing strings fall back to English and then to the key itself.
+#[derive(Resource)]
+pub struct Localization {
+    language: Language,
+    tables: HashMap<Language, StringTable>,
+    /// Keys already reported as missing in debug builds, so that each is reported only once.
+    reported: Mutex<HashSet<(Language, String)>>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        let tables = Language::ALL.iter()
+            .map(|&language| {
+                let table = ron::from_str(language.source()).unwrap_or_else(|error| {
+                    error!("Failed to parse strings of {language:?}: {error}");
+                    StringTable::default()
+                });
+                (language, table)
+            })
+            .collect();
+
+        Localization {
+            language: Language::default(),
+            tables,
+            reported: Mutex::default(),
+        }
+    }
+}
+
+impl Localization {
+    pub fn text(&self, key: &str) -> String {
+        self.format(key, &[])
+    }
+
+    /// Replaces the `{name}` placeholders of the string with the arguments.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let text = self.lookup(key, |_, table| table.strings.get(key).map(String::as_str));
+        fill(text, args)
+    }
+
+    /// Picks the form of the string by the plural rules of the language, `{count}` is filled in too.
+    pub fn plural(&self, key: &str, count: u32, args: &[(&str, &dyn Display)]) -> String {
+        let text = self.lookup(key, |language, table| {
+            table.plurals.get(key).map(|forms| forms.get(language.plural_category(count)))
+        });
+
+        let mut args = args.to_vec();
+        args.push(("count", &count));
+        fill(text, &args)
+    }
+
+    fn lookup<'a>(&'a self, key: &'a str, get: impl Fn(Language, &'a StringTable) -> Option<&'a str>) -> &'a str {
+        for language in [self.language, Language::English] {
+            if let Some(text) = self.tables.get(&language).and_then(|table| get(language, table)) {
+                return text;
+            }
+            self.report_missing(language, key);
+        }
+
+        key
+    }
+
+    fn report_missing(&self, language: Language, key: &str) {
+        if !cfg!(debug_assertions) { return; }
+
+        let Ok(mut reported) = self.reported.lock() else { return };
+        if reported.insert((language, key.to_string())) {
+            warn!("Missing {language:?} string {key}");
+        }
+    }
+}
+
+fn fill(text: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(text.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
+
+/// Sent when the player picks another language, so that the screens can be rebuilt.
+#[derive(Event, Debug)]
+pub struct LanguageChanged;
+
+/// Button which switches to the next language.
+#[derive(Component, Debug)]
+pub struct ToggleLanguage;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Localization>()
+            .add_event::<LanguageChanged>()
+            .add_systems(Startup, (replace_default_font, apply_profile_language))
+            .add_systems(Update, toggle_language.run_if(in_state(GameState::Menu).or_else(in_state(GameState::GameOver))));
+
+        #[cfg(debug_assertions)]
+        app.add_systems(Startup, report_missing_strings);
+    }
+}
+
+fn replace_default_font(mut fonts: ResMut<Assets<Font>>) {
+    match Font::try_from_bytes(FONT.to_vec()) {
+        Ok(font) => fonts.insert(Handle::<Font>::default(), font),
+        Err(error) => error!("Failed to load the font: {error:?}"),
+    }
+}
+
+fn apply_profile_language(
+    profile: Res<Profile>,
+    mut localization: ResMut<Localization>,
+) {
+    localization.language = profile.language;
+}
+
+fn toggle_language(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ToggleLanguage>)>,
+    mut localization: ResMut<Localization>,
+    mut profile: ResMut<Profile>,
+    mut events: EventWriter<LanguageChanged>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed { continue; }
+
+        localization.language = localization.language.next();
+        profile.language = localization.language;
+        save_profile(&profile);
+        events.send(LanguageChanged);
+    }
+}
+
+/// Compares the other languages with English, which is the reference for the keys.
+#[cfg(debug_assertions)]
+fn report_missing_strings(localization: Res<Localization>) {
+    let Some(english) = localization.tables.get(&Language::English) else { return };
+    let mut report = Vec::new();
+
+    for language in Language::ALL.into_iter().filter(|&language| language != Language::English) {
+        let Some(table) = localization.tables.get(&language) else { continue };
+
+        for key in english.strings.keys().filter(|key| !table.strings.contains_key(*key)) {
+            report.push(format!("{language:?} is missing string {key}"));
+        }
+        for key in table.strings.keys().filter(|key| !english.strings.contains_key(*key)) {
+            report.push(format!("{language:?} has string {key}, which English does not"));
+        }
+
+        let needs_few = (0..100).any(|count| language.plural_category(count) == PluralCategory::Few);
+        for key in english.plurals.keys() {
+            match table.plurals.get(key) {
+                None => report.push(format!("{language:?} is missing plural {key}")),
+                Some(forms) if needs_few && forms.few.is_none() => report.push(format!("{language:?} is missing the few form of plural {key}")),
+                Some(_) => {},
+            }
+        }
+        for key in table.plurals.keys().filter(|key| !english.plurals.contains_key(*key)) {
+            report.push(format!("{language:?} has plural {key}, which English does not"));
+        }
+    }
+
+    if report.is_empty() {
+        info!("All languages have all the strings");
+        return;
+    }
+
+    report.sort();
+    warn!("{} strings are missing or extra:\n{}", report.len(), report.join("\n"));
+}
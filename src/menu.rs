@@ -1,5 +1,9 @@
+use crate::achievements::ToggleAchievementList;
 use crate::loading::TextureAssets;
+use crate::localization::{LanguageChanged, Localization, ToggleLanguage};
 use crate::music::MusicAudio;
+use crate::parents::{QueueOrdering, ToggleQueueOrdering};
+use crate::waves::{ShiftMode, ToggleShiftMode};
 use crate::GameState;
 use bevy::prelude::*;
 use bevy_kira_audio::AudioInstance;
@@ -14,6 +18,7 @@ impl Plugin for MenuPlugin {
             .add_systems(OnEnter(GameState::Menu), setup_menu)
             .add_systems(OnEnter(GameState::GameOver), setup_menu)
             .add_systems(Update, (click_play_button, click_music_button).run_if(in_state(GameState::Menu).or_else(in_state(GameState::GameOver))))
+            .add_systems(Update, (cleanup_menu, setup_menu).chain().run_if(on_event::<LanguageChanged>()))
             .add_systems(OnExit(GameState::Menu), cleanup_menu)
             .add_systems(OnExit(GameState::GameOver), cleanup_menu);
     }
@@ -42,6 +47,9 @@ fn setup_menu(
     textures: Res<TextureAssets>,
     game_state: Res<State<GameState>>,
     score: Res<crate::score::Score>,
+    queue_ordering: Res<QueueOrdering>,
+    shift_mode: Res<ShiftMode>,
+    localization: Res<Localization>,
 ) {
     info!("menu");
 
@@ -68,8 +76,8 @@ fn setup_menu(
             children.spawn(( 
                 TextBundle::from_section(
                     match game_state {
-                        GameState::Menu => "THERE IS NOT MUSH ROOM IN THIS DAYCARE",
-                        GameState::GameOver => "GAME OVER",
+                        GameState::Menu => localization.text("menu.title"),
+                        GameState::GameOver => localization.text("menu.game_over"),
                         _ => unreachable!(),
                     },
                     TextStyle {
@@ -87,11 +95,11 @@ fn setup_menu(
                     },
                     ..default()
                 });
-                let text = if score.1 == 1 {
-                    format!("You managed to take care of only one fungus!\nFinal score: {}", score.0)
-                } else {
-                    format!("You managed to take care of {} fungi!\nFinal score: {}", score.1, score.0)
-                };
+                let text = format!(
+                    "{}\n{}",
+                    localization.plural("menu.fungi", score.1.max(0) as u32, &[]),
+                    localization.format("menu.final_score", &[("score", &score.0)]),
+                );
                 children.spawn(( 
                     TextBundle::from_section(
                         text,
@@ -130,8 +138,8 @@ fn setup_menu(
                 .with_children(|parent| {
                     parent.spawn(TextBundle::from_section(
                         match game_state {
-                            GameState::Menu => "Play",
-                            GameState::GameOver => "Retry",
+                            GameState::Menu => localization.text("menu.play"),
+                            GameState::GameOver => localization.text("menu.retry"),
                             _ => unreachable!(),
                         },
                         TextStyle {
@@ -141,6 +149,10 @@ fn setup_menu(
                         },
                     ));
                 });
+            spawn_menu_button(children, localization.text("menu.achievements"), ToggleAchievementList, &button_colors);
+            spawn_menu_button(children, localization.text(queue_ordering.label_key()), ToggleQueueOrdering, &button_colors);
+            spawn_menu_button(children, localization.text(shift_mode.label_key()), ToggleShiftMode, &button_colors);
+            spawn_menu_button(children, localization.text("menu.language"), ToggleLanguage, &button_colors);
             if game_state == &GameState::GameOver {
                 children.spawn(NodeBundle {
                     style: Style {
@@ -166,7 +178,7 @@ fn setup_menu(
                 ))
                 .with_children(|parent| {
                     parent.spawn(TextBundle::from_section(
-                        "Menu",
+                        localization.text("menu.menu"),
                         TextStyle {
                             font_size: 40.0,
                             color: Color::rgb(0.9, 0.9, 0.9),
@@ -201,7 +213,7 @@ fn setup_menu(
                     ))
                     .with_children(|parent| {
                         parent.spawn(TextBundle::from_section(
-                            "Exit",
+                            localization.text("menu.exit"),
                             TextStyle {
                                 font_size: 40.0,
                                 color: Color::rgb(0.9, 0.9, 0.9),
@@ -297,7 +309,7 @@ fn setup_menu(
                 ))
                 .with_children(|parent| {
                     parent.spawn(TextBundle::from_section(
-                        "Made with Bevy",
+                        localization.text("menu.made_with_bevy"),
                         TextStyle {
                             font_size: 15.0,
                             color: Color::rgb(0.9, 0.9, 0.9),
@@ -335,7 +347,7 @@ fn setup_menu(
                 ))
                 .with_children(|parent| {
                     parent.spawn(TextBundle::from_section(
-                        "Made in 48 hours during Matfyz Spring Game Jam 2024",
+                        localization.text("menu.game_jam"),
                         TextStyle {
                             font_size: 15.0,
                             color: Color::rgb(0.9, 0.9, 0.9),
@@ -365,7 +377,7 @@ fn setup_menu(
                 ))
                 .with_children(|parent| {
                     parent.spawn(TextBundle::from_section(
-                        "Open source",
+                        localization.text("menu.open_source"),
                         TextStyle {
                             font_size: 15.0,
                             color: Color::rgb(0.9, 0.9, 0.9),
@@ -384,6 +396,43 @@ fn setup_menu(
         });
 }
 
+/// Wide button of the settings column, with a gap above it.
+fn spawn_menu_button(children: &mut ChildBuilder, label: String, marker: impl Component, button_colors: &ButtonColors) {
+    children.spawn(NodeBundle {
+        style: Style {
+            height: Val::Px(20.0),
+            ..default()
+        },
+        ..default()
+    });
+    children
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(260.0),
+                    height: Val::Px(50.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: button_colors.normal.into(),
+                ..Default::default()
+            },
+            button_colors.clone(),
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: 40.0,
+                    color: Color::rgb(0.9, 0.9, 0.9),
+                    ..default()
+                },
+            ));
+        });
+}
+
 #[derive(Component)]
 struct ChangeState(GameState);
 
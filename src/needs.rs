@@ -1,25 +1,28 @@
 use bevy::prelude::*;
 
-use crate::{child::Child, growing::{Growable, GROW_STAGES}, hitbox::*, loading::TextureAssets, GameState};
+use crate::{child::Child, garden_events::GardenConditions, growing::{Growable, GROW_STAGES}, hitbox::*, loading::TextureAssets, particles::{spawn_particles, ParticleEmitter}, tuning::Tuning, tween::{Ease, Tween, TweenTarget}, GameState};
 
-const HUNGER_DECREASE_RATE: f32 = 1.0;
-const HUNGER_FULL_VALUE: f32 = 15.0;
 const HUNGER_BUBBLE_OFFSET: Vec2 = Vec2::new(48.0, 96.0);
-const FOOD_SOURCE_SPAWN_POS: Vec2 = Vec2::new(750.0, 90.0);
-
-const THIRST_DECREASE_RATE: f32 = 1.0;
-const THIRST_FULL_VALUE: f32 = 10.0;
 const THIRST_BUBBLE_OFFSET: Vec2 = Vec2::new(-48.0, 96.0);
-const WATER_SOURCE_SPAWN_POS: Vec2 = Vec2::new(750.0, -250.0);
 
 const BUBBLES_Z: f32 = 6.0;
 
+/// Tools fly back into their bucket above everything and sink into it once there.
+const TOOL_RETURN_Z: f32 = 5.0;
+const TOOL_RETURN_ARC_HEIGHT: f32 = 120.0;
+const TOOL_RETURN_DURATION: f32 = 0.45;
+
+/// How long a knocked over bucket stays on the ground.
+const KNOCKED_OVER_DURATION: f32 = 6.0;
+const KNOCKED_OVER_ANGLE: f32 = 1.3;
+/// Scale of a planted child, which a gulp settles back to.
+const CHILD_REST_SCALE: Vec3 = Vec3::ONE;
+
 /// Size of food/water.
 const ITEM_SIZE: Vec2 = Vec2::splat(128.0);
 /// Size of hunger/thirst bubble.
 const BUBBLE_SIZE: Vec2 = Vec2::splat(64.0);
 const SOURCE_SIZE: Vec2 = Vec2::splat(128.0);
-const HITBOX_SIZE: Vec2 = Vec2::splat(128.0);
 
 pub struct NeedsPlugin;
 
@@ -30,8 +33,91 @@ pub struct Needs {
 
     thirst: f32,
     thirst_bubble: Option<Entity>,
+
+    history: NeedsHistory,
+}
+
+/// What the child went through while growing up.
+#[derive(Debug)]
+pub struct NeedsHistory {
+    pub times_hungry: u32,
+    /// Lowest thirst reached, as a fraction of full thirst.
+    pub lowest_thirst: f32,
+}
+
+impl Default for NeedsHistory {
+    fn default() -> Self {
+        NeedsHistory {
+            times_hungry: 0,
+            lowest_thirst: 1.0,
+        }
+    }
+}
+
+impl Needs {
+    pub fn new(tuning: &Tuning) -> Self {
+        Self {
+            hunger: tuning.hunger_full,
+            hunger_bubble: None,
+
+            thirst: tuning.thirst_full,
+            thirst_bubble: None,
+
+            history: NeedsHistory::default(),
+        }
+    }
+
+    pub fn history(&self) -> &NeedsHistory {
+        &self.history
+    }
+
+    /// Whether a hunger or thirst bubble is shown above the child.
+    pub fn has_bubble(&self) -> bool {
+        self.hunger_bubble.is_some() || self.thirst_bubble.is_some()
+    }
+
+    /// Fills the hunger up and pops the hunger bubble, if there is one.
+    pub fn feed(&mut self, commands: &mut Commands, tuning: &Tuning) {
+        self.hunger = tuning.hunger_full;
+        if let Some(bubble) = self.hunger_bubble.take() {
+            commands.entity(bubble).despawn();
+        }
+    }
+
+    /// Fills the thirst up and pops the thirst bubble, if there is one.
+    pub fn quench(&mut self, commands: &mut Commands, tuning: &Tuning) {
+        self.thirst = tuning.thirst_full;
+        if let Some(bubble) = self.thirst_bubble.take() {
+            commands.entity(bubble).despawn();
+        }
+    }
+}
+
+/// Source of food or water. Refills its tool, unless it has been knocked over.
+#[derive(Component)]
+pub struct Bucket {
+    tool: Entity,
+    full_texture: Handle<Image>,
+    empty_texture: Handle<Image>,
 }
 
+/// Bucket lies on the ground and its tool can't be used until it is picked up again.
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+pub struct KnockedOver {
+    timer: Timer,
+}
+
+/// Asks for a bucket to be knocked over.
+#[derive(Event, Debug)]
+pub struct KnockOverBucketEvent {
+    pub bucket: Entity,
+}
+
+/// Sent when a hunger or thirst bubble appears above a child.
+#[derive(Event, Debug)]
+pub struct NeedBubbleEvent;
+
 #[derive(Component)]
 struct Food;
 
@@ -41,115 +127,170 @@ struct Water;
 impl Plugin for NeedsPlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_event::<NeedBubbleEvent>()
+            .add_event::<KnockOverBucketEvent>()
             .add_systems(OnEnter(GameState::Playing), spawn_bucket)
             .add_systems(Update, (
                 handle_needs_decrease,
-                read_on_drop_events
+                read_on_drop_events,
+                read_knock_over_events,
+                pick_up_buckets,
+                apply_tuning_to_buckets,
             ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
     }
 }
 
-impl Default for Needs {
-    fn default() -> Self {
-        Self {
-            hunger: HUNGER_FULL_VALUE,
-            hunger_bubble: None,
-
-            thirst: THIRST_FULL_VALUE,
-            thirst_bubble: None,
-        }
+fn tool_draggable() -> Draggable {
+    Draggable {
+        must_intersect_with: Some(Layer::Child.into()),
+        drag_opaque: true,
+        ..default()
     }
 }
 
-fn spawn_bucket(mut commands: Commands, textures: Res<TextureAssets>) {
-    // spawn food source
-    commands.spawn((
-        SpriteBundle {
-            texture: textures.bucket_full.clone(),
-            sprite: Sprite {
-                custom_size: Some(SOURCE_SIZE),
-                ..default()
-            },
-            transform: Transform::from_translation(FOOD_SOURCE_SPAWN_POS.extend(1.0)),
-            ..default()
-        },
-        crate::GameObject,
-    ));
+fn spawn_bucket(mut commands: Commands, textures: Res<TextureAssets>, tuning: Res<Tuning>) {
+    let food_source_pos = Vec2::from(tuning.food_source_pos);
+    let water_source_pos = Vec2::from(tuning.water_source_pos);
+    let tool_hitbox_size = Vec2::splat(tuning.tool_hitbox_size);
 
     // spawn food into food source
-    commands.spawn((
+    let food = commands.spawn((
         SpriteBundle {
             texture: textures.worm.clone(),
-            transform: Transform::from_translation(FOOD_SOURCE_SPAWN_POS.extend(-10.0)),
+            transform: Transform::from_translation(food_source_pos.extend(-10.0)),
             sprite: Sprite {
                 custom_size: Some(ITEM_SIZE),
                 ..default()
             },
             ..default()
         },
-        Hitbox::new_centered(HITBOX_SIZE),
+        Hitbox::new_centered(tool_hitbox_size),
         InLayers::new_single(Layer::Tool),
-        Draggable {
-            must_intersect_with: Some(Layer::Child.into()),
-            drag_opaque: true,
-            ..default()
-        },
+        tool_draggable(),
         Food,
         crate::GameObject,
-    ));
+    )).id();
 
-    // spawn water source
+    // spawn food source
     commands.spawn((
         SpriteBundle {
-            texture: textures.bucket_water_leaves.clone(),
+            texture: textures.bucket_full.clone(),
             sprite: Sprite {
                 custom_size: Some(SOURCE_SIZE),
                 ..default()
             },
-            transform: Transform::from_translation(WATER_SOURCE_SPAWN_POS.extend(1.0)),
+            transform: Transform::from_translation(food_source_pos.extend(1.0)),
             ..default()
         },
+        Bucket {
+            tool: food,
+            full_texture: textures.bucket_full.clone(),
+            empty_texture: textures.bucket_empty.clone(),
+        },
         crate::GameObject,
     ));
 
     // spawn water into water source
-    commands.spawn((
+    let water = commands.spawn((
         SpriteBundle {
             texture: textures.water_leaves.clone(),
-            transform: Transform::from_translation(WATER_SOURCE_SPAWN_POS.extend(-10.0)),
+            transform: Transform::from_translation(water_source_pos.extend(-10.0)),
             sprite: Sprite {
                 custom_size: Some(ITEM_SIZE),
                 ..default()
             },
             ..default()
         },
-        Hitbox::new_centered(HITBOX_SIZE),
+        Hitbox::new_centered(tool_hitbox_size),
         InLayers::new_single(Layer::Tool),
-        Draggable {
-            must_intersect_with: Some(Layer::Child.into()),
-            drag_opaque: true,
+        tool_draggable(),
+        Water,
+        crate::GameObject,
+    )).id();
+
+    // spawn water source
+    commands.spawn((
+        SpriteBundle {
+            texture: textures.bucket_water_leaves.clone(),
+            sprite: Sprite {
+                custom_size: Some(SOURCE_SIZE),
+                ..default()
+            },
+            transform: Transform::from_translation(water_source_pos.extend(1.0)),
             ..default()
         },
-        Water,
+        Bucket {
+            tool: water,
+            full_texture: textures.bucket_water_leaves.clone(),
+            empty_texture: textures.bucket_water.clone(),
+        },
         crate::GameObject,
     ));
 }
 
+fn read_knock_over_events(
+    mut commands: Commands,
+    mut events: EventReader<KnockOverBucketEvent>,
+    mut buckets: Query<(&Bucket, &mut Handle<Image>, &mut Transform), Without<KnockedOver>>,
+    tools: Query<&Draggable>,
+) {
+    for event in events.read() {
+        let Ok((bucket, mut texture, mut transform)) = buckets.get_mut(event.bucket) else { continue };
+
+        // the tool is in the player's hand, the child missed
+        let Ok(tool) = tools.get(bucket.tool) else { continue };
+        if tool.drag_shadow.is_some() { continue; }
+
+        *texture = bucket.empty_texture.clone();
+        transform.rotation = Quat::from_rotation_z(KNOCKED_OVER_ANGLE);
+        commands.entity(bucket.tool).remove::<Draggable>();
+        commands.entity(event.bucket).insert(KnockedOver {
+            timer: Timer::from_seconds(KNOCKED_OVER_DURATION, TimerMode::Once),
+        });
+    }
+}
+
+fn pick_up_buckets(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut buckets: Query<(Entity, &Bucket, &mut KnockedOver, &mut Handle<Image>, &mut Transform)>,
+    mut tool_sprites: Query<&mut Sprite>,
+) {
+    for (entity, bucket, mut knocked_over, mut texture, mut transform) in &mut buckets {
+        knocked_over.timer.tick(time.delta());
+        if !knocked_over.timer.finished() { continue; }
+
+        *texture = bucket.full_texture.clone();
+        transform.rotation = Quat::IDENTITY;
+        commands.entity(entity).remove::<KnockedOver>();
+        commands.entity(bucket.tool).insert(tool_draggable());
+
+        // the tool might have lost its hover shadow together with the draggable
+        if let Ok(mut sprite) = tool_sprites.get_mut(bucket.tool) {
+            sprite.color.set_a(1.0);
+        }
+    }
+}
+
 fn handle_needs_decrease(
     mut commands: Commands,
     time: Res<Time>, 
     textures: Res<TextureAssets>,
-    mut query: Query<(Entity, &mut Needs, &mut Growable)>
+    mut query: Query<(Entity, &mut Needs, &mut Growable)>,
+    mut bubble_events: EventWriter<NeedBubbleEvent>,
+    conditions: Res<GardenConditions>,
+    tuning: Res<Tuning>,
 ) {
     for (entity, mut needs, mut growable) in &mut query {
         if growable.stage == GROW_STAGES - 1 {
             continue;
         }
 
-        needs.hunger -= time.delta_seconds() * HUNGER_DECREASE_RATE;
-        needs.thirst -= time.delta_seconds() * THIRST_DECREASE_RATE;
+        needs.hunger -= time.delta_seconds() * tuning.hunger_rate * conditions.hunger_rate_mult;
+        needs.thirst -= time.delta_seconds() * tuning.thirst_rate * conditions.thirst_rate_mult;
 
         growable.stopped_by_needs = needs.hunger < 0.0 || needs.thirst < 0.0;
+        needs.history.lowest_thirst = needs.history.lowest_thirst.min(needs.thirst / tuning.thirst_full);
 
         if needs.hunger < 0.0 && needs.hunger_bubble.is_none() {
             needs.hunger_bubble = Some(commands.spawn(
@@ -166,6 +307,8 @@ fn handle_needs_decrease(
 
             commands.entity(entity).add_child(needs.hunger_bubble.unwrap());
             commands.entity(needs.hunger_bubble.unwrap()).set_parent(entity);
+            needs.history.times_hungry += 1;
+            bubble_events.send(NeedBubbleEvent);
         }
 
         if needs.thirst < 0.0 && needs.thirst_bubble.is_none() {
@@ -183,44 +326,79 @@ fn handle_needs_decrease(
 
             commands.entity(entity).add_child(needs.thirst_bubble.unwrap());
             commands.entity(needs.thirst_bubble.unwrap()).set_parent(entity);
+            bubble_events.send(NeedBubbleEvent);
         }
     }
 }
 
+fn return_to_bucket_tween(from: Vec3, bucket: Vec2) -> Tween {
+    let above_bucket = bucket.extend(TOOL_RETURN_Z);
+    Tween::new(TweenTarget::Arc { from: from.truncate().extend(TOOL_RETURN_Z), to: above_bucket, height: TOOL_RETURN_ARC_HEIGHT }, TOOL_RETURN_DURATION, Ease::QuadInOut)
+        .then(TweenTarget::Translation { from: above_bucket, to: bucket.extend(-10.0) }, 0.0, Ease::Linear)
+}
+
+/// Child puffs up a little when its need is satisfied.
+/// Always settles at the rest scale, so gulps cut short by another tween don't leave the child bigger.
+fn gulp_tween(from: Vec3) -> Tween {
+    let puffed = CHILD_REST_SCALE * Vec3::new(1.15, 1.15, 1.0);
+    Tween::new(TweenTarget::Scale { from, to: puffed }, 0.1, Ease::QuadOut)
+        .then(TweenTarget::Scale { from: puffed, to: CHILD_REST_SCALE }, 0.3, Ease::BackOut)
+}
+
 fn read_on_drop_events(
     mut commands: Commands,
     mut events: EventReader<DropEvent>,
-    mut child_query: Query<&mut Needs, With<Child>>,
-    mut food_query: Query<&mut Transform, (With<Food>, Without<Water>)>,
-    mut water_query: Query<&mut Transform, (With<Water>, Without<Food>)>,
+    mut child_query: Query<(&mut Needs, &Transform), With<Child>>,
+    food_query: Query<&Transform, (With<Food>, Without<Water>)>,
+    water_query: Query<&Transform, (With<Water>, Without<Food>)>,
+    tuning: Res<Tuning>,
 ) {
     for event in events.read() {
-        if let Ok(mut transform) = food_query.get_mut(event.dropped_entity) {
-            transform.translation = FOOD_SOURCE_SPAWN_POS.extend(-10.0);
+        if let Ok(transform) = food_query.get(event.dropped_entity) {
+            commands.entity(event.dropped_entity).insert(return_to_bucket_tween(transform.translation, Vec2::from(tuning.food_source_pos)));
 
-            let mut needs = child_query.get_mut(event.dropped_on_entity).unwrap();
+            let (mut needs, child_transform) = child_query.get_mut(event.dropped_on_entity).unwrap();
 
             if needs.hunger_bubble.is_none() {
                 continue;
             }
 
-            needs.hunger = HUNGER_FULL_VALUE;
-            commands.entity(needs.hunger_bubble.unwrap()).despawn();
-            needs.hunger_bubble = None;
+            needs.feed(&mut commands, &tuning);
+            spawn_particles(&mut commands, child_transform.translation.truncate(), ParticleEmitter::crumbs());
+            commands.entity(event.dropped_on_entity).insert(gulp_tween(child_transform.scale));
         }
 
-        if let Ok(mut transform) = water_query.get_mut(event.dropped_entity) {
-            transform.translation = WATER_SOURCE_SPAWN_POS.extend(-10.0);
+        if let Ok(transform) = water_query.get(event.dropped_entity) {
+            commands.entity(event.dropped_entity).insert(return_to_bucket_tween(transform.translation, Vec2::from(tuning.water_source_pos)));
 
-            let mut needs = child_query.get_mut(event.dropped_on_entity).unwrap();
+            let (mut needs, child_transform) = child_query.get_mut(event.dropped_on_entity).unwrap();
 
             if needs.thirst_bubble.is_none() {
                 continue;
             }
 
-            needs.thirst = THIRST_FULL_VALUE;
-            commands.entity(needs.thirst_bubble.unwrap()).despawn();
-            needs.thirst_bubble = None;
+            needs.quench(&mut commands, &tuning);
+            spawn_particles(&mut commands, child_transform.translation.truncate(), ParticleEmitter::splash());
+            commands.entity(event.dropped_on_entity).insert(gulp_tween(child_transform.scale));
         }
     }
-}
\ No newline at end of file
+}
+
+/// Moves the buckets and resizes their tools when the tuning changes.
+/// Buckets lying on the ground and tools on their way back are left alone, not to teleport them mid-animation.
+fn apply_tuning_to_buckets(
+    tuning: Res<Tuning>,
+    mut bucket_query: Query<(&Bucket, &mut Transform), (Without<KnockedOver>, Without<Tween>)>,
+    mut tool_query: Query<(&mut Transform, &mut Hitbox, Has<Food>), (Without<Bucket>, Without<Tween>)>,
+) {
+    if !tuning.is_changed() { return; }
+
+    for (bucket, mut transform) in &mut bucket_query {
+        let Ok((mut tool_transform, mut hitbox, is_food)) = tool_query.get_mut(bucket.tool) else { continue };
+        let position = Vec2::from(if is_food { tuning.food_source_pos } else { tuning.water_source_pos });
+
+        transform.translation = position.extend(transform.translation.z);
+        tool_transform.translation = position.extend(tool_transform.translation.z);
+        *hitbox = Hitbox::new_centered(Vec2::splat(tuning.tool_hitbox_size));
+    }
+}
@@ -0,0 +1,80 @@
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use rand::{thread_rng, Rng};
+
+/// How much faster grumpy parents lose patience while a need bubble is in sight.
+pub const GRUMPY_DRAIN_MULT: f32 = 2.0;
+
+/// Pays triple, but has only half the patience.
+#[derive(Component, Debug)]
+pub struct Vip;
+
+/// Relaxed and very patient.
+#[derive(Component, Debug)]
+pub struct Grandparent;
+
+/// Loses patience faster when seeing neglected children.
+#[derive(Component, Debug)]
+pub struct Grumpy;
+
+/// Personality of a parent, on top of its species.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ParentArchetype {
+    Regular,
+    Vip,
+    Grandparent,
+    Grumpy,
+}
+
+impl ParentArchetype {
+    pub fn random() -> Self {
+        match thread_rng().gen_range(0..10) {
+            0 => ParentArchetype::Vip,
+            1 => ParentArchetype::Grandparent,
+            2 => ParentArchetype::Grumpy,
+            _ => ParentArchetype::Regular,
+        }
+    }
+
+    pub fn patience_mult(&self) -> f32 {
+        match self {
+            ParentArchetype::Vip => 0.5,
+            ParentArchetype::Grandparent => 2.0,
+            _ => 1.0,
+        }
+    }
+
+    pub fn score_mult(&self) -> f32 {
+        match self {
+            ParentArchetype::Vip => 3.0,
+            _ => 1.0,
+        }
+    }
+
+    /// Multiplies the colors of the patience bar.
+    pub fn bar_tint(&self) -> Vec3 {
+        match self {
+            ParentArchetype::Regular => Vec3::ONE,
+            ParentArchetype::Vip => Vec3::new(1.2, 1.0, 0.3),
+            ParentArchetype::Grandparent => Vec3::new(0.6, 0.9, 1.6),
+            ParentArchetype::Grumpy => Vec3::new(1.0, 0.3, 1.1),
+        }
+    }
+
+    pub fn bar_border_color(&self) -> Color {
+        match self {
+            ParentArchetype::Regular => Color::rgb(0.4, 0.2, 0.2),
+            ParentArchetype::Vip => Color::rgb(1.0, 0.8, 0.2),
+            ParentArchetype::Grandparent => Color::rgb(0.5, 0.7, 1.0),
+            ParentArchetype::Grumpy => Color::rgb(0.6, 0.1, 0.5),
+        }
+    }
+
+    pub fn insert_marker(&self, commands: &mut EntityCommands) {
+        match self {
+            ParentArchetype::Regular => {},
+            ParentArchetype::Vip => { commands.insert(Vip); },
+            ParentArchetype::Grandparent => { commands.insert(Grandparent); },
+            ParentArchetype::Grumpy => { commands.insert(Grumpy); },
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use bevy::{prelude::*, sprite::Anchor, text::Text2dBounds};
+use rand::{thread_rng, Rng};
+
+use crate::{child::Child, hitbox::Draggable, localization::Localization, needs::Needs, parents::{Departing, Parent}, pulsing::Pulsing, species::{ChildGrowthEvent, GrowthStep}, GameState};
+
+/// Chance of a newly arrived parent having a special request.
+const REQUEST_CHANCE: f64 = 0.3;
+/// Growth stage of teenagers.
+const TEENAGER_STAGE: usize = 3;
+/// Thirst, as a fraction of full, which a well watered child never drops below.
+const WELL_WATERED_THIRST: f32 = 0.25;
+
+const REQUEST_MET_SCORE_MULT: f32 = 1.5;
+const REQUEST_FAILED_SCORE_MULT: f32 = 0.5;
+
+const BUBBLE_OFFSET: Vec3 = Vec3::new(0.0, -120.0, 2.0);
+const BUBBLE_SIZE: Vec2 = Vec2::new(130.0, 44.0);
+const BUBBLE_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.9);
+const BUBBLE_TEXT_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+
+pub struct ParentRequestsPlugin;
+
+/// Special wish of a parent about how their child should be raised.
+#[derive(Component, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ParentRequest {
+    /// Wants the child back already at the given growth stage.
+    PickupAtStage(usize),
+    /// The child must never have been hungry.
+    NeverHungry,
+    /// The child must never have been close to being thirsty.
+    WellWatered,
+}
+
+impl ParentRequest {
+    fn random() -> Self {
+        match thread_rng().gen_range(0..3) {
+            0 => ParentRequest::PickupAtStage(TEENAGER_STAGE),
+            1 => ParentRequest::NeverHungry,
+            _ => ParentRequest::WellWatered,
+        }
+    }
+
+    /// Key of the bubble text in the string tables.
+    fn text_key(&self) -> &'static str {
+        match self {
+            ParentRequest::PickupAtStage(TEENAGER_STAGE) => "requests.teenager",
+            ParentRequest::PickupAtStage(_) => "requests.early",
+            ParentRequest::NeverHungry => "requests.never_hungry",
+            ParentRequest::WellWatered => "requests.well_watered",
+        }
+    }
+
+    pub fn is_met(&self, stage: usize, needs: &Needs) -> bool {
+        match self {
+            ParentRequest::PickupAtStage(pickup_stage) => stage == *pickup_stage,
+            ParentRequest::NeverHungry => needs.history().times_hungry == 0,
+            ParentRequest::WellWatered => needs.history().lowest_thirst >= WELL_WATERED_THIRST,
+        }
+    }
+
+    /// Bonus for meeting the request, penalty for ignoring it.
+    pub fn score_multiplier(&self, stage: usize, needs: &Needs) -> f32 {
+        if self.is_met(stage, needs) {
+            REQUEST_MET_SCORE_MULT
+        } else {
+            REQUEST_FAILED_SCORE_MULT
+        }
+    }
+}
+
+/// Speech bubble with the request of the parent.
+#[derive(Component)]
+struct RequestBubble;
+
+impl Plugin for ParentRequestsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                assign_requests,
+                allow_early_pickup,
+                remove_bubbles_of_departing,
+            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
+    }
+}
+
+fn assign_requests(
+    mut commands: Commands,
+    query: Query<Entity, Added<Parent>>,
+    localization: Res<Localization>,
+) {
+    for entity in &query {
+        if !thread_rng().gen_bool(REQUEST_CHANCE) { continue; }
+
+        let request = ParentRequest::random();
+
+        let bubble = commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(BUBBLE_OFFSET),
+                sprite: Sprite {
+                    color: BUBBLE_COLOR,
+                    custom_size: Some(BUBBLE_SIZE),
+                    ..default()
+                },
+                ..default()
+            },
+            RequestBubble,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text::from_section(
+                    localization.text(request.text_key()),
+                    TextStyle {
+                        font_size: 16.0,
+                        color: BUBBLE_TEXT_COLOR,
+                        ..default()
+                    },
+                ).with_justify(JustifyText::Center),
+                text_anchor: Anchor::Center,
+                text_2d_bounds: Text2dBounds { size: BUBBLE_SIZE - Vec2::splat(8.0) },
+                transform: Transform::from_translation(Vec3::Z * 0.1),
+                ..default()
+            });
+        }).id();
+
+        commands.entity(entity)
+            .insert(request)
+            .add_child(bubble);
+    }
+}
+
+/// Children of parents who want them back early can be delivered once they reach the stage.
+fn allow_early_pickup(
+    mut commands: Commands,
+    mut growth_events: EventReader<ChildGrowthEvent>,
+    mut child_query: Query<(&Child, &mut Draggable)>,
+    request_query: Query<&ParentRequest>,
+) {
+    for event in growth_events.read() {
+        let GrowthStep::StageChanged(stage) = event.step else { continue };
+        let Ok((child, mut draggable)) = child_query.get_mut(event.child) else { continue };
+        let Ok(ParentRequest::PickupAtStage(pickup_stage)) = request_query.get(child.parent_entity) else { continue };
+
+        if stage != *pickup_stage || draggable.special_allowed_entities.contains(&child.parent_entity) { continue; }
+
+        draggable.special_allowed_entities.push(child.parent_entity);
+        commands.entity(event.child).insert(Pulsing {
+            min: 0.95,
+            max: 1.05,
+            speed: 0.2,
+        });
+    }
+}
+
+fn remove_bubbles_of_departing(
+    mut commands: Commands,
+    parent_query: Query<&Children, Added<Departing>>,
+    bubble_query: Query<(), With<RequestBubble>>,
+) {
+    for children in &parent_query {
+        for child in children.iter() {
+            if bubble_query.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use std::time::Duration;
 
 use rand::prelude::*;
-use bevy::{prelude::*, sprite::Anchor};
-use bevy_progressbar::{ProgressBar, ProgressBarBundle, ProgressBarMaterial};
+use bevy::{ecs::system::SystemParam, prelude::*, sprite::Anchor};
+use serde::Deserialize;
 
 use crate::{
-    animations::AnimationBundle, child::*, difficulty::{Difficulty, START_PARENT_SPAWN_TIME, START_PATIENCE}, growing::Growable, highlight::Highlightable, hitbox::*, loading::*, needs::*, pulsing::Pulsing, GameState
+    animations::{Animation, AnimationBundle, AnimationFrames, AnimationPart, AnimationState}, child::*, child_walking::ChildWalking, difficulty::{Difficulty, START_PARENT_SPAWN_TIME, START_PATIENCE}, growing::{Growable, HypnoDespawnEvent}, highlight::Highlightable, parent_archetypes::{Grumpy, ParentArchetype, Vip, GRUMPY_DRAIN_MULT}, parent_requests::ParentRequest, hitbox::*, loading::*, localization::Localization, needs::*, particles::{spawn_particles, ParticleEmitter}, pulsing::Pulsing, tuning::Tuning, tween::{Ease, Repeat, Tween, TweenTarget}, waves::WaveTimeline, world_bar::{WorldBar, WorldBarBundle}, GameState
 };
 
 pub const MAX_PARENTS: usize = 13;
@@ -13,6 +13,8 @@ pub const MAX_PARENTS: usize = 13;
 // Maybe in future replace with texture size?
 pub const PARENT_SIZE: Vec2 = Vec2::new(128.0, 256.0);
 pub const PARENT_WALK_SPEED: f32 = 100.0;
+/// Angry parents storm off quickly, so the game doesn't drag on.
+pub const PARENT_ANGRY_WALK_SPEED: f32 = 400.0;
 /// Gap between parents in the parent waiting queue.
 pub const PARENT_GAP: f32 = 10.0;
 /// Score received at max patiance.
@@ -26,23 +28,133 @@ pub const PARENT_QUEUE_X: f32 = -850.0;
 const FLOATY_NORMALCY_BIAS: f32 = 0.015;
 /// How much the color of the bar wobbles
 const FLOATY_COLOR_SCALE: f32 = 0.03;
-/// Color section count of the bar
-const BAR_SECTIONS: usize = 200;
+/// Color section count of the bar, each section is a sprite of its own.
+const BAR_SECTIONS: usize = 12;
 /// Height of patience bar in pixels.
 const BAR_HEIGHT: f32 = 20.0;
 /// Width of the patience bar in pixels.
 const BAR_WIDTH: f32 = PARENT_SIZE.x - 20.0;
-/// Y offset of patience bar from parent.
-const BAR_OFFSET: f32 = 70.0;
-
-#[derive(Clone, Copy, Eq, PartialEq)]
-enum ParentState {
+/// Position of the center of the patience bar relative to the parent.
+const BAR_OFFSET: Vec3 = Vec3::new(0.0, -90.0, 1.0);
+/// Where the delivered child holds on to its parent.
+const CARRIED_CHILD_OFFSET: Vec3 = Vec3::new(50.0, -110.0, 0.2);
+const CARRIED_CHILD_SCALE: f32 = 0.6;
+/// Shortest time between two parents, whatever the wave or the tuning says.
+const MIN_PARENT_SPAWN_TIME: f32 = 0.5;
+/// Leaving parents walk in front of the queue.
+const DEPARTING_Z: f32 = 60.0;
+/// How far parents tilt when wiggling with joy, in radians.
+const ARRIVAL_WIGGLE_ANGLE: f32 = 0.08;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ParentState {
+    /// On the way to the queue.
     Walking,
+    /// Just arrived and glad to be here.
+    Happy,
     Patient,
-    Nervous
+    Worried,
+    Nervous,
+    /// Ran out of patience and is leaving without the child.
+    Furious,
+    /// Got the child back and is leaving.
+    Delighted,
+}
+
+impl ParentState {
+    /// States driven by the patience of a waiting parent.
+    fn is_waiting(&self) -> bool {
+        matches!(self, ParentState::Happy | ParentState::Patient | ParentState::Worried | ParentState::Nervous)
+    }
+
+    pub fn can_transition_to(&self, next: ParentState) -> bool {
+        match self {
+            ParentState::Walking => next == ParentState::Happy,
+            _ if self.is_waiting() => next != ParentState::Walking,
+            // leaving for good
+            _ => false,
+        }
+    }
+
+    /// Which of the drawn animations the state uses, for body and eyes.
+    fn poses(&self) -> (AnimationState, AnimationState) {
+        match self {
+            ParentState::Walking => (AnimationState::Walking, AnimationState::Walking),
+            ParentState::Happy => (AnimationState::Patient, AnimationState::Patient),
+            ParentState::Patient => (AnimationState::Patient, AnimationState::Patient),
+            ParentState::Worried => (AnimationState::Patient, AnimationState::Nervous),
+            ParentState::Nervous => (AnimationState::Nervous, AnimationState::Nervous),
+            ParentState::Furious => (AnimationState::Walking, AnimationState::Nervous),
+            ParentState::Delighted => (AnimationState::Walking, AnimationState::Patient),
+        }
+    }
+
+    /// Moods without their own drawings are told apart by color.
+    fn tint(&self) -> Color {
+        match self {
+            ParentState::Happy => Color::rgb(1.0, 1.0, 0.85),
+            ParentState::Worried => Color::rgb(0.95, 0.95, 1.0),
+            ParentState::Furious => Color::rgb(1.0, 0.55, 0.5),
+            ParentState::Delighted => Color::rgb(1.0, 0.9, 0.6),
+            _ => Color::WHITE,
+        }
+    }
+}
+
+/// Mood of a parent. Changes of the state switch the parent's animations.
+#[derive(Component, Debug)]
+pub struct ParentMood {
+    state: ParentState,
+}
+
+impl ParentMood {
+    /// Moves to the next state if the transition is allowed.
+    /// Check with [`ParentMood::can_transition_to`] first to avoid triggering change detection.
+    fn transition(&mut self, next: ParentState) {
+        if self.can_transition_to(next) {
+            self.state = next;
+        }
+    }
+
+    fn can_transition_to(&self, next: ParentState) -> bool {
+        self.state != next && self.state.can_transition_to(next)
+    }
 }
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+/// Fractions of remaining patience at which waiting parents change their mood.
+#[derive(Resource, Debug)]
+pub struct MoodThresholds {
+    /// Parents stay happy until their patience drops below this.
+    pub happy: f32,
+    pub worried: f32,
+    pub nervous: f32,
+}
+
+impl Default for MoodThresholds {
+    fn default() -> Self {
+        MoodThresholds {
+            happy: 0.85,
+            worried: 0.5,
+            nervous: 0.25,
+        }
+    }
+}
+
+impl MoodThresholds {
+    fn mood_for(&self, patience_remaining: f32) -> ParentState {
+        if patience_remaining >= self.happy {
+            ParentState::Happy
+        } else if patience_remaining >= self.worried {
+            ParentState::Patient
+        } else if patience_remaining >= self.nervous {
+            ParentState::Worried
+        } else {
+            ParentState::Nervous
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
 pub enum Species {
     Derp,
     Psycho,
@@ -56,9 +168,9 @@ pub struct Parent {
     /// Position of parent in parent queue/
     queue_index: usize,
     patience_timer: Timer,
-    state: ParentState,
     species: Species,
-    is_changed: bool
+    /// Reached the queue for the first time and brought the spores.
+    arrived: bool,
 }
 
 #[derive(Component)]
@@ -68,12 +180,49 @@ struct Walker {
     destination: Vec2,
 }
 
+/// Parent is walking off the screen and despawns once it gets there.
+#[derive(Component)]
+pub struct Departing {
+    angry: bool,
+}
+
+/// Sent when a grown child is handed back to its parent.
+#[derive(Event, Debug)]
+pub struct ChildDeliveredEvent {
+    pub child: Entity,
+    pub species: Species,
+}
+
 #[derive(Resource)]
 struct ParentSpawnTimer(Timer);
 
 #[derive(Resource, Default)]
 struct ParentQueue([bool; MAX_PARENTS]);
 
+/// How the waiting parents are placed in the queue.
+#[derive(Resource, Default, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum QueueOrdering {
+    /// Parents pick a random free slot and stay there.
+    #[default]
+    RandomSlots,
+    /// Parents line up and move forward when somebody in front of them leaves.
+    Line,
+}
+
+impl QueueOrdering {
+    /// Key of the button label in the string tables.
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            QueueOrdering::RandomSlots => "menu.queue_random",
+            QueueOrdering::Line => "menu.queue_line",
+        }
+    }
+}
+
+/// Button which switches the queue ordering.
+#[derive(Component, Debug)]
+pub struct ToggleQueueOrdering;
+
 impl Plugin for ParentsPlugin {
     fn build(&self, app: &mut App) {
         app
@@ -84,15 +233,30 @@ impl Plugin for ParentsPlugin {
                 )
             ))
             .init_resource::<ParentQueue>()
+            .init_resource::<MoodThresholds>()
+            .init_resource::<QueueOrdering>()
+            .add_event::<ChildDeliveredEvent>()
             .add_systems(OnEnter(GameState::Playing), cleanup_parent_system)
             .add_systems(OnExit(GameState::Playing), cleanup_parent_system) // better safe than sorry
             .add_systems(Update, (
                 handle_random_parent_spawning,
+                compact_queue.before(move_walkers),
                 move_walkers,
+                carry_spores.after(move_walkers),
                 update_patience,
                 read_on_drop_events,
                 update_parent_animations,
-            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
+            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))))
+            .add_systems(Update, toggle_queue_ordering.run_if(in_state(GameState::Menu).or_else(in_state(GameState::GameOver))));
+    }
+}
+
+impl Parent {
+    /// Gives back part of the patience, as a fraction of the whole.
+    pub fn restore_patience(&mut self, fraction: f32) {
+        let restored = self.patience_timer.duration().mul_f32(fraction);
+        let elapsed = self.patience_timer.elapsed().saturating_sub(restored);
+        self.patience_timer.set_elapsed(elapsed);
     }
 }
 
@@ -101,16 +265,12 @@ impl Default for Parent {
         Self { 
             queue_index: 0,
             patience_timer: Timer::from_seconds(START_PATIENCE, TimerMode::Once),
-            state: ParentState::Walking,
             species: Species::Derp,
-            is_changed: false
+            arrived: false,
         }
     }
 }
 
-#[derive(Component, Debug)]
-pub struct PatienceBar;
-
 #[derive(Component, Debug)]
 pub struct HasPatienceBar(Entity);
 
@@ -123,92 +283,41 @@ fn cleanup_parent_system(
 }
 
 fn handle_random_parent_spawning(
-    mut commands: Commands,
+    mut traffic: ParentTraffic,
     time: Res<Time>,
     mut timer: ResMut<ParentSpawnTimer>,
-    mut parent_queue: ResMut<ParentQueue>,
-    mut bar_materials: ResMut<Assets<ProgressBarMaterial>>,
     animation_assets: Res<AnimationAssets>,
     difficulty: Res<Difficulty>,
-    camera: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    queue_ordering: Res<QueueOrdering>,
+    timeline: Res<WaveTimeline>,
 ) {
-    let available_slots_indices = parent_queue.0.iter().enumerate().filter_map(|(i, &slot)| if !slot { Some(i) } else { None }).collect::<Vec<_>>();
+    // nobody comes in between the waves
+    let Some(spawn_interval) = timeline.spawn_interval(&difficulty) else { return; };
+
+    let available_slots_indices = traffic.parent_queue.0.iter().enumerate().filter_map(|(i, &slot)| if !slot { Some(i) } else { None }).collect::<Vec<_>>();
     if available_slots_indices.is_empty() { return; }
     let fraction_empty: f64 = available_slots_indices.len() as f64 / MAX_PARENTS as f64;
-    let pick_first = thread_rng().gen_bool(fraction_empty * fraction_empty);
+    let pick_first = *queue_ordering == QueueOrdering::Line || thread_rng().gen_bool(fraction_empty * fraction_empty);
     let picked_slot = if pick_first {
         available_slots_indices.first().copied().unwrap()
     } else {
         available_slots_indices.choose(&mut rand::thread_rng()).copied().unwrap()
     };
 
+    // the pace follows the current wave even in the middle of waiting
+    timer.0.set_duration(Duration::from_secs_f32(spawn_interval.max(MIN_PARENT_SPAWN_TIME)));
     timer.0.tick(time.delta());
-    if timer.0.just_finished() || parent_queue.0.iter().all(|&slot| !slot) {
-        timer.0.set_duration(Duration::from_secs_f32(difficulty.parent_spawn_time));
+    if timer.0.just_finished() || traffic.parent_queue.0.iter().all(|&slot| !slot) {
         timer.0.reset();
 
-        let (camera, camera_transform) = camera.single();
-        let spawn_x = camera.viewport_to_world_2d(camera_transform, Vec2::new(-PARENT_SIZE.x, 0.0)).unwrap().x;
         let spawn_pos = Vec3::new(
-            spawn_x,
+            traffic.spawn_x(),
             PARENT_SPAWN_Y,
             50.0
         );
-        parent_queue.0[picked_slot] = true;
-
-        let mut floaty_shift = Vec3::new(0.0, 0.0, 0.0);
-        let bar_colors = (0..BAR_SECTIONS).map(|i| {
-            let p = i as f32 / BAR_SECTIONS as f32;
-            let r = 0.6 + (1.0 - p) * 0.4 + floaty_shift.x;
-            let g = 0.3 + p * 0.4 + floaty_shift.y;
-            let b = 0.2 + floaty_shift.z;
-
-            floaty_shift += Vec3::new(
-                rand::thread_rng().gen_range(-1.0..=1.0) * FLOATY_COLOR_SCALE - floaty_shift.x * FLOATY_NORMALCY_BIAS,
-                rand::thread_rng().gen_range(-1.0..=1.0) * FLOATY_COLOR_SCALE - floaty_shift.y * FLOATY_NORMALCY_BIAS,
-                rand::thread_rng().gen_range(-1.0..=1.0) * FLOATY_COLOR_SCALE - floaty_shift.z * FLOATY_NORMALCY_BIAS,
-            );
-
-            (1, Color::rgb(r, g, b))
-        }).collect::<Vec<_>>();
-        let mut bar_bar = ProgressBar::new(bar_colors);
-        bar_bar.set_progress(1.0);
-        let bar_style = Style {
-            position_type: PositionType::Absolute,
-            width: Val::Vw((BAR_WIDTH - 4.0) / crate::WINDOW_WIDTH * 100.0),
-            height: Val::Vh((BAR_HEIGHT - 4.0) / crate::WINDOW_HEIGHT * 100.0),
-            ..bevy_utils::default()
-        };
-
-        let bar_container = commands.spawn((NodeBundle {
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    width: Val::Vw(BAR_WIDTH / crate::WINDOW_WIDTH * 100.0),
-                    height: Val::Vh(BAR_HEIGHT / crate::WINDOW_HEIGHT * 100.0),
-                    top: Val::Px(BAR_OFFSET),
-                    left: Val::Px(-10000.0),
-                    border: UiRect::all(Val::Px(2.)),
-                    ..bevy_utils::default()
-                },
-                border_color: Color::rgb(0.4, 0.2, 0.2).into(),
-                background_color: Color::rgb(0.2, 0.1, 0.1).into(),
-                ..default()
-            },
-            crate::GameObject,
-        )).id();
-
-        let patience_bar = commands.spawn((
-            ProgressBarBundle::new(
-                bar_style,
-                bar_bar,
-                &mut bar_materials,
-            ),
-            PatienceBar,
-            crate::GameObject,
-        )).id();
-
-        commands.get_entity(bar_container).unwrap().add_child(patience_bar);
+        traffic.parent_queue.0[picked_slot] = true;
 
+        let archetype = ParentArchetype::random();
         let species = match rand::thread_rng().next_u32() % 3 {
             0 => Species::Derp,
             1 => Species::Psycho,
@@ -216,39 +325,74 @@ fn handle_random_parent_spawning(
             _ => Species::Derp
         };
 
-        spawn_parent(&mut commands, difficulty, &animation_assets, species, spawn_pos, picked_slot, patience_bar);
+        spawn_parent(&mut traffic.commands, difficulty, &animation_assets, species, archetype, spawn_pos, picked_slot);
     }
 }
 
+/// Patience bar in the colors of the archetype, with a little random wobble to them.
+fn patience_bar(archetype: ParentArchetype) -> WorldBar {
+    let tint = archetype.bar_tint();
+
+    let mut floaty_shift = Vec3::new(0.0, 0.0, 0.0);
+    let bar_colors = (0..BAR_SECTIONS).map(|i| {
+        let p = i as f32 / BAR_SECTIONS as f32;
+        let r = 0.6 + (1.0 - p) * 0.4 + floaty_shift.x;
+        let g = 0.3 + p * 0.4 + floaty_shift.y;
+        let b = 0.2 + floaty_shift.z;
+
+        floaty_shift += Vec3::new(
+            rand::thread_rng().gen_range(-1.0..=1.0) * FLOATY_COLOR_SCALE - floaty_shift.x * FLOATY_NORMALCY_BIAS,
+            rand::thread_rng().gen_range(-1.0..=1.0) * FLOATY_COLOR_SCALE - floaty_shift.y * FLOATY_NORMALCY_BIAS,
+            rand::thread_rng().gen_range(-1.0..=1.0) * FLOATY_COLOR_SCALE - floaty_shift.z * FLOATY_NORMALCY_BIAS,
+        );
+
+        Color::rgb(r * tint.x, g * tint.y, b * tint.z)
+    }).collect::<Vec<_>>();
+
+    WorldBar::new(Vec2::new(BAR_WIDTH, BAR_HEIGHT), bar_colors)
+        .with_border_color(archetype.bar_border_color())
+}
+
+fn slot_position(slot: usize) -> Vec2 {
+    Vec2::new(PARENT_QUEUE_X, PARENT_SPAWN_Y)
+        + Vec2::X * ((PARENT_SIZE.x + PARENT_GAP) * slot as f32)
+}
+
 fn spawn_parent(
     commands: &mut Commands,
     difficulty: Res<Difficulty>,
     animation_assets: &AnimationAssets,
     species: Species,
+    archetype: ParentArchetype,
     spawn_pos: Vec3,
     slot: usize,
-    patience_bar: Entity
 ) {
-    let parent = commands.spawn((
+    let mut parent = commands.spawn((
         Parent {
             queue_index: slot,
             species,
-            patience_timer: Timer::from_seconds(difficulty.parent_patience, TimerMode::Once),
+            patience_timer: Timer::from_seconds(difficulty.parent_patience * archetype.patience_mult(), TimerMode::Once),
             ..default()
         },
+        ParentMood {
+            state: ParentState::Walking,
+        },
         SpatialBundle {
             transform: Transform::from_translation(spawn_pos),
             ..default()
         },
         Walker {
-            destination: Vec2::new(PARENT_QUEUE_X, PARENT_SPAWN_Y)
-                + Vec2::X * ((PARENT_SIZE.x + PARENT_GAP) * slot as f32),
+            destination: slot_position(slot),
         },
         InLayers::new_single(Layer::Parent),
-        HasPatienceBar(patience_bar),
         crate::GameObject,
         Highlightable::default(),
-    )).id();
+    ));
+    archetype.insert_marker(&mut parent);
+
+    let patience_bar = parent.commands().spawn(WorldBarBundle::new(patience_bar(archetype), BAR_OFFSET)).id();
+    parent.insert(HasPatienceBar(patience_bar)).add_child(patience_bar);
+    let parent = parent.id();
 
     spawn_animations(parent, commands, animation_assets, species, ParentState::Walking);
 }
@@ -265,148 +409,160 @@ fn spawn_animations(
     
     let animation_body = commands.spawn((
         AnimationBundle::new(animation_body, 0.15, 0.2, 0.0),
+        BodyVisual,
         )).id();
 
     let animation_eyes = commands.spawn((
         AnimationBundle::new(animation_eyes, 0.15, 0.2, 0.1),
+        EyesVisual,
         )).id();
 
     commands.entity(parent).push_children(&[animation_body, animation_eyes]);
 }
 
-fn get_animation(animation_assets: &AnimationAssets, species: Species, state: ParentState, eyes: bool) -> Vec<Handle<Image>> {
-    match species {
-        Species::Derp => get_derp_animation(animation_assets, state, eyes),
-        Species::Psycho => get_psycho_animation(animation_assets, state, eyes),
-        Species::Poser => get_poser_animation(animation_assets, state, eyes)
-    }
-}
-
-fn get_derp_animation(animation_assets: &AnimationAssets, state: ParentState, eyes: bool) -> Vec<Handle<Image>> {
-    match state {
-        ParentState::Walking =>
-        if eyes {
-            animation_assets.derp_parent_walking_eyes.clone()
-        } else {
-            animation_assets.derp_parent_walking_body.clone()
-        },
+fn get_animation(animation_assets: &AnimationAssets, species: Species, state: ParentState, eyes: bool) -> AnimationFrames {
+    let (body_pose, eyes_pose) = state.poses();
 
-        ParentState::Patient =>
-        if eyes {
-            animation_assets.derp_parent_patient_eyes.clone()
-        } else {
-            animation_assets.derp_parent_patient_body.clone()
-        },
-
-        ParentState::Nervous =>
-        if eyes {
-            animation_assets.derp_parent_nervous_eyes.clone()
-        } else {
-            animation_assets.derp_parent_nervous_body.clone()
-        },
+    if eyes {
+        animation_assets.get(species, eyes_pose, AnimationPart::Eyes)
+    } else {
+        animation_assets.get(species, body_pose, AnimationPart::Body)
     }
 }
 
-fn get_psycho_animation(animation_assets: &AnimationAssets, state: ParentState, eyes: bool) -> Vec<Handle<Image>> {
-    match state {
-        ParentState::Walking =>
-        if eyes {
-            animation_assets.psycho_parent_walking_eyes.clone()
-        } else {
-            animation_assets.psycho_parent_walking_body.clone()
-        },
+fn update_parent_animations(
+    parent_query: Query<(&Parent, &ParentMood, &Children), Changed<ParentMood>>,
+    mut body_query: Query<(&mut Animation, &mut Sprite), (With<BodyVisual>, Without<EyesVisual>)>,
+    mut eyes_query: Query<(&mut Animation, &mut Sprite), (With<EyesVisual>, Without<BodyVisual>)>,
+    animation_assets: Res<AnimationAssets>
+) {
+    for (parent, mood, children) in parent_query.iter() {
+        let tint = mood.state.tint();
 
-        ParentState::Patient =>
-        if eyes {
-            animation_assets.psycho_parent_patient_eyes.clone()
-        } else {
-            animation_assets.psycho_parent_patient_body.clone()
-        },
+        for child in children.iter() {
+            if let Ok((mut animation, mut sprite)) = body_query.get_mut(*child) {
+                animation.change_frames(get_animation(&animation_assets, parent.species, mood.state, false));
+                sprite.color = tint;
+            }
 
-        ParentState::Nervous =>
-        if eyes {
-            animation_assets.psycho_parent_nervous_eyes.clone()
-        } else {
-            animation_assets.psycho_parent_nervous_body.clone()
-        },
+            if let Ok((mut animation, mut sprite)) = eyes_query.get_mut(*child) {
+                animation.change_frames(get_animation(&animation_assets, parent.species, mood.state, true));
+                sprite.color = tint;
+            }
+        }
     }
 }
 
-fn get_poser_animation(animation_assets: &AnimationAssets, state: ParentState, eyes: bool) -> Vec<Handle<Image>> {
-    match state {
-        ParentState::Walking =>
-        if eyes {
-            animation_assets.poser_parent_walking_eyes.clone()
-        } else {
-            animation_assets.poser_parent_walking_body.clone()
-        },
+/// The parent queue together with what it takes to bring parents to the screen and send them off it.
+#[derive(SystemParam)]
+struct ParentTraffic<'w, 's> {
+    commands: Commands<'w, 's>,
+    parent_queue: ResMut<'w, ParentQueue>,
+    camera: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<Camera2d>>,
+}
 
-        ParentState::Patient =>
-        if eyes {
-            animation_assets.poser_parent_patient_eyes.clone()
-        } else {
-            animation_assets.poser_parent_patient_body.clone()
-        },
+/// Parts of a parent which change when it leaves.
+struct DepartingParent<'a> {
+    entity: Entity,
+    parent: &'a Parent,
+    mood: &'a mut ParentMood,
+    transform: &'a mut Transform,
+    highlightable: Option<&'a Highlightable>,
+    patience_bar: Option<Entity>,
+}
 
-        ParentState::Nervous =>
-        if eyes {
-            animation_assets.poser_parent_nervous_eyes.clone()
-        } else {
-            animation_assets.poser_parent_nervous_body.clone()
-        },
+impl ParentTraffic<'_, '_> {
+    /// X position just behind the left edge of the screen.
+    fn spawn_x(&self) -> f32 {
+        let (camera, camera_transform) = self.camera.single();
+        camera.viewport_to_world_2d(camera_transform, Vec2::new(-PARENT_SIZE.x, 0.0)).unwrap().x
     }
-}
 
-fn update_parent_animations(
-    mut commands: Commands,
-    mut parent_query: Query<(Entity, &mut Parent)>,
-    children_query: Query<&Children>,
-    animation_assets: Res<AnimationAssets>
-) {
-    for (entity, mut parent) in parent_query.iter_mut() {
-        if !parent.is_changed { continue; }
+    /// X position just behind the right edge of the screen.
+    fn exit_x(&self) -> f32 {
+        let (camera, camera_transform) = self.camera.single();
+        let viewport_width = camera.logical_viewport_size().map(|size| size.x).unwrap_or(crate::WINDOW_WIDTH);
+        camera.viewport_to_world_2d(camera_transform, Vec2::new(viewport_width + PARENT_SIZE.x, 0.0)).unwrap().x
+    }
 
-        for child in children_query.iter_descendants(entity) {
-            if let Some(mut child_commands) = commands.get_entity(child) {
-                child_commands.despawn();
+    /// Sends the parent walking off the screen. Frees its place in the queue and removes everything
+    /// which would let the player interact with it.
+    fn start_departure(&mut self, departing: DepartingParent, angry: bool) {
+        let exit_x = self.exit_x();
+        let DepartingParent { entity, parent, mood, transform, highlightable, patience_bar } = departing;
+        self.parent_queue.0[parent.queue_index] = false;
+        mood.transition(if angry { ParentState::Furious } else { ParentState::Delighted });
+        transform.translation.z = DEPARTING_Z;
+
+        if let Some(highlight) = highlightable.and_then(|highlightable| highlightable.highlight_entity) {
+            if let Some(highlight) = self.commands.get_entity(highlight) {
+                highlight.despawn_recursive();
             }
         }
 
-        spawn_animations(entity, &mut commands, &animation_assets, parent.species, parent.state);
-        parent.is_changed = false;
+        if let Some(patience_bar) = patience_bar {
+            self.commands.entity(patience_bar).despawn_recursive();
+        }
+
+        self.commands.entity(entity)
+            .remove::<(Hitbox, Highlightable, HasPatienceBar)>()
+            .insert((
+                Walker {
+                    destination: Vec2::new(exit_x, transform.translation.y),
+                },
+                Departing { angry },
+            ));
     }
 }
 
 fn move_walkers(
     mut commands: Commands, 
     time: Res<Time>, 
-    textures: Res<TextureAssets>,
-    mut query: Query<(Entity, &mut Parent, &mut Transform, &Walker)>
+    animation_assets: Res<AnimationAssets>,
+    mut query: Query<(Entity, &mut Parent, &mut ParentMood, &mut Transform, &Walker, Option<&Departing>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+    tuning: Res<Tuning>,
 ) {
-    for (entity, mut parent, mut transform, walker) in &mut query {
-        let direction = (walker.destination.extend(0.0) - transform.translation).normalize();
-        transform.translation += direction * PARENT_WALK_SPEED * time.delta_seconds();
+    for (entity, mut parent, mut mood, mut transform, walker, departing) in &mut query {
+        let speed = match departing {
+            Some(Departing { angry: true }) => PARENT_ANGRY_WALK_SPEED,
+            _ => PARENT_WALK_SPEED,
+        };
 
-        if Vec2::distance(transform.translation.xy(), walker.destination) < PARENT_WALK_SPEED * time.delta_seconds() {
-            transform.translation = walker.destination.extend(0.0);
+        let direction = (walker.destination - transform.translation.xy()).normalize_or_zero();
+        transform.translation += direction.extend(0.0) * speed * time.delta_seconds();
+
+        if Vec2::distance(transform.translation.xy(), walker.destination) < speed * time.delta_seconds() {
             commands.entity(entity).remove::<Walker>();
 
-            commands.entity(entity).insert(Hitbox::new_centered(Vec2::splat(128.0)));
+            if let Some(departing) = departing {
+                commands.entity(entity).despawn_recursive();
+
+                if departing.angry {
+                    next_state.set(GameState::GameOver);
+                }
 
-            let spores_texture = match parent.species {
-                Species::Derp => textures.derp_spores.clone(),
-                Species::Psycho => textures.psycho_spores.clone(),
-                Species::Poser => textures.poser_spores.clone()
-            };
+                continue;
+            }
+
+            // only moving forward in the line
+            if parent.arrived {
+                transform.translation = walker.destination.extend(0.0);
+                continue;
+            }
+
+            transform.translation = walker.destination.extend(0.0);
+
+            commands.entity(entity).insert(Hitbox::new_centered(Vec2::splat(128.0)));
 
             let mut spore_transform = *transform;
             spore_transform.translation += Vec3::new(0.0, 0.0, 1.5);
-            let mut anim =AnimationBundle::new_with_size(vec![ spores_texture.clone() ], 0.15, CHILD_HITBOX_SIZE, 0.15);
+            let mut anim =AnimationBundle::new_with_size(animation_assets.get(parent.species, AnimationState::Spores, AnimationPart::Body), 0.15, tuning.child_hitbox_size, 0.15);
             anim.sprite_sheet.transform = spore_transform;
 
             let child_entity = commands.spawn((
                 anim,
-                Hitbox::new_centered(Vec2::splat(CHILD_HITBOX_SIZE)),
+                Hitbox::new_centered(Vec2::splat(tuning.child_hitbox_size)),
                 EmitsCollisions::default(),
                 Draggable {
                     must_be_contained_in: Some(Layer::Garden.into()),
@@ -417,18 +573,17 @@ fn move_walkers(
                     parent_entity: entity,
                     species: parent.species
                 },
-                Needs::default(),
+                Needs::new(&tuning),
                 DropBlocker,
                 crate::GameObject,
                 Pulsing {
                     min: 0.9,
                     max: 1.3,
                     speed: 1.1,
-                    ..default()
                 },
             )).id();
             
-            let mut anim = AnimationBundle::new_with_size(vec![ textures.nothing.clone() ], 0.1, CHILD_HITBOX_SIZE, 0.6);
+            let mut anim = AnimationBundle::new_with_size(animation_assets.get(parent.species, AnimationState::Spores, AnimationPart::Eyes), 0.1, tuning.child_hitbox_size, 0.6);
             anim.sprite_sheet.transform = Transform::from_translation(Vec3::new(0.0, 0.0, 5.0)).with_scale(Vec3::new(1.5, 1.5, 1.0));
             anim.sprite_sheet.sprite.anchor = Anchor::BottomCenter;
             let eyes_visual = commands.spawn((
@@ -438,84 +593,178 @@ fn move_walkers(
 
             commands.entity(child_entity).add_child(eyes_visual);
 
-            parent.state = ParentState::Patient;
-            parent.is_changed = true;
+            parent.arrived = true;
+            mood.transition(ParentState::Happy);
+
+            // little wiggle of joy upon arrival
+            commands.entity(entity).insert(
+                Tween::new(TweenTarget::Rotation { from: Quat::IDENTITY, to: Quat::from_rotation_z(ARRIVAL_WIGGLE_ANGLE) }, 0.12, Ease::SineInOut)
+                    .with_yoyo()
+                    .with_repeat(Repeat::Times(2))
+            );
+        }
+    }
+}
+
+/// Moves parents forward in the line to fill the gaps left by those who have left.
+fn compact_queue(
+    mut commands: Commands,
+    queue_ordering: Res<QueueOrdering>,
+    mut parent_queue: ResMut<ParentQueue>,
+    mut query: Query<(Entity, &mut Parent, Option<&mut Walker>), Without<Departing>>,
+) {
+    if *queue_ordering != QueueOrdering::Line { return; }
+
+    let mut parents = query.iter_mut().collect::<Vec<_>>();
+    parents.sort_by_key(|(_, parent, _)| parent.queue_index);
+
+    for (entity, parent, walker) in parents.iter_mut() {
+        let Some(free_slot) = (0..parent.queue_index).find(|&slot| !parent_queue.0[slot]) else { continue };
+
+        parent_queue.0[parent.queue_index] = false;
+        parent_queue.0[free_slot] = true;
+        parent.queue_index = free_slot;
+
+        match walker {
+            Some(walker) => walker.destination = slot_position(free_slot),
+            None => {
+                commands.entity(*entity).insert(Walker {
+                    destination: slot_position(free_slot),
+                });
+            }
+        }
+    }
+}
+
+/// Spores waiting to be planted stay in front of their parent when it moves forward in the line.
+fn carry_spores(
+    parent_query: Query<(&Parent, &Transform), (Without<Departing>, Without<Child>)>,
+    mut spore_query: Query<(&Child, &mut Transform), Without<Growable>>,
+) {
+    for (child, mut transform) in &mut spore_query {
+        let Ok((parent, parent_transform)) = parent_query.get(child.parent_entity) else { continue };
+        if !parent.arrived { continue; }
+
+        transform.translation = parent_transform.translation.truncate().extend(transform.translation.z);
+    }
+}
+
+fn toggle_queue_ordering(
+    interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<ToggleQueueOrdering>)>,
+    mut texts: Query<&mut Text>,
+    mut queue_ordering: ResMut<QueueOrdering>,
+    localization: Res<Localization>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction != Interaction::Pressed { continue; }
+
+        *queue_ordering = match *queue_ordering {
+            QueueOrdering::RandomSlots => QueueOrdering::Line,
+            QueueOrdering::Line => QueueOrdering::RandomSlots,
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = localization.text(queue_ordering.label_key());
+            }
         }
     }
 }
 
 fn update_patience(
+    mut traffic: ParentTraffic,
     time: Res<Time>,
-    mut query: Query<(&mut Parent, &Transform, Option<&HasPatienceBar>, Option<&Walker>)>,
-    mut bars: Query<(&mut ProgressBar, &bevy::prelude::Parent), (With<PatienceBar>, Without<Parent>)>,
-    mut styles: Query<&mut Style, (Without<Parent>, Without<ProgressBar>)>,
-    camera: Query<(&Camera, &GlobalTransform), (With<Camera2d>, Without<Parent>, Without<PatienceBar>)>,
-    mut next_state: ResMut<NextState<GameState>>,
+    thresholds: Res<MoodThresholds>,
+    mut query: Query<(Entity, &mut Parent, &mut ParentMood, &mut Transform, Option<&HasPatienceBar>, Option<&Highlightable>, Option<&Departing>, Option<&Grumpy>)>,
+    needs_query: Query<&Needs>,
+    mut bars: Query<&mut WorldBar>,
 ) {
-    // moving the bar really shouldn't be here but I'm too lazy to refactor it
-    let (camera, camera_trans) = camera.single();
-    for (mut parent, trans, patience_bar, walker) in &mut query {
-        if let Some(patience_bar) = patience_bar {
-            if let Ok((mut bar, ui_parent)) = bars.get_mut(patience_bar.0) {
-                bar.set_progress(parent.patience_timer.fraction_remaining());
-                while bar.sections.len() > (parent.patience_timer.fraction_remaining() * BAR_SECTIONS as f32) as usize {
-                    bar.sections.pop();
-                }
-                let bar_trans = trans.translation - Vec3::Y * BAR_OFFSET - Vec3::new(BAR_WIDTH / 2.0, BAR_HEIGHT / 2.0, 0.0);
-                let bar_pos = camera.world_to_viewport(camera_trans, bar_trans).unwrap();
-                let mut style = styles.get_mut(ui_parent.get()).unwrap();
-                style.left = Val::Px(bar_pos.x);
-                style.top = Val::Px(bar_pos.y);
+    let bubble_in_sight = needs_query.iter().any(Needs::has_bubble);
+    for (entity, mut parent, mut mood, mut trans, patience_bar, highlightable, departing, grumpy) in &mut query {
+        let patience_bar = patience_bar.map(|patience_bar| patience_bar.0);
+        if let Some(mut bar) = patience_bar.and_then(|patience_bar| bars.get_mut(patience_bar).ok()) {
+            let progress = parent.patience_timer.fraction_remaining();
+            if bar.progress != progress {
+                bar.progress = progress;
             }
         }
 
-        // no impatience until you arrive
-        if walker.is_some() { continue };
+        // no impatience until you arrive, nor once you are leaving
+        if !parent.arrived || departing.is_some() { continue };
 
-        parent.patience_timer.tick(time.delta());
+        let drain = if grumpy.is_some() && bubble_in_sight { GRUMPY_DRAIN_MULT } else { 1.0 };
+        parent.patience_timer.tick(time.delta().mul_f32(drain));
 
-        if parent.patience_timer.remaining() < parent.patience_timer.duration() / 2 && parent.state != ParentState::Nervous {
-            parent.state = ParentState::Nervous;
-            parent.is_changed = true;
+        let next_mood = thresholds.mood_for(parent.patience_timer.fraction_remaining());
+        if mood.can_transition_to(next_mood) {
+            mood.transition(next_mood);
         }
 
         if parent.patience_timer.just_finished() {
-            next_state.set(GameState::GameOver);
+            traffic.start_departure(
+                DepartingParent {
+                    entity,
+                    parent: &parent,
+                    mood: &mut mood,
+                    transform: &mut trans,
+                    highlightable,
+                    patience_bar,
+                },
+                true,
+            );
         }
     }
 }
 
 fn read_on_drop_events(
-    mut commands: Commands,
-    mut parent_queue: ResMut<ParentQueue>,
+    mut traffic: ParentTraffic,
     mut events: EventReader<DropEvent>,
-    child_query: Query<&Child, With<Growable>>,
-    parent_query: Query<(&Parent, Option<&HasPatienceBar>)>,
-    bars: Query<(&ProgressBar, &bevy::prelude::Parent)>,
+    child_query: Query<(&Child, &Growable, &Needs)>,
+    mut parent_query: Query<(&Parent, &mut ParentMood, &mut Transform, Option<&HasPatienceBar>, Option<&Highlightable>, Option<&ParentRequest>, Option<&Vip>)>,
     mut score: ResMut<crate::score::Score>,
+    mut delivered_events: EventWriter<ChildDeliveredEvent>,
+    mut hypno_despawn_events: EventWriter<HypnoDespawnEvent>,
 ) {
     for event in events.read() {
-        if let Ok(children) = child_query.get(event.dropped_entity) {
+        if let Ok((children, growable, needs)) = child_query.get(event.dropped_entity) {
             if children.parent_entity.index() != event.dropped_on_entity.index() {
                 continue;
             }
-            let (parent, maybe_bar) = parent_query.get(children.parent_entity).unwrap();
+            let Ok((parent, mut mood, mut transform, maybe_bar, highlightable, request, vip)) = parent_query.get_mut(children.parent_entity) else { continue };
 
             let remains = parent.patience_timer.remaining().as_secs_f32();
             let max_score_mult = remains / parent.patience_timer.duration().as_secs_f32();
+            let request_mult = request.map_or(1.0, |request| request.score_multiplier(growable.stage, needs));
+            let vip_mult = if vip.is_some() { ParentArchetype::Vip.score_mult() } else { 1.0 };
+
+            traffic.start_departure(
+                DepartingParent {
+                    entity: children.parent_entity,
+                    parent,
+                    mood: &mut mood,
+                    transform: &mut transform,
+                    highlightable,
+                    patience_bar: maybe_bar.map(|bar| bar.0),
+                },
+                false,
+            );
 
-            parent_queue.0[parent.queue_index] = false;
-            commands.entity(children.parent_entity).despawn_recursive();
-            commands.entity(event.dropped_entity).despawn_recursive();
-
-            if let Some(bar) = maybe_bar {
-                let (_, bar_parent_border) = bars.get(bar.0).unwrap();
-                commands.entity(bar_parent_border.get()).despawn_recursive();
-                commands.entity(bar.0).despawn_recursive();
-            }
+            // the child holds on to its parent and stops doing anything on its own
+            hypno_despawn_events.send(HypnoDespawnEvent { parent: event.dropped_entity });
+            traffic.commands.entity(event.dropped_entity)
+                .remove::<(Growable, Draggable, Hitbox, EmitsCollisions, DropBlocker, InLayers, Pulsing, ChildWalking)>()
+                .insert(Transform::from_translation(CARRIED_CHILD_OFFSET).with_scale(Vec3::splat(CARRIED_CHILD_SCALE)))
+                .set_parent(children.parent_entity);
 
-            score.0 += (PARENT_MAX_PATIENCE_SCORE * max_score_mult) as i32;
+            score.0 += (PARENT_MAX_PATIENCE_SCORE * max_score_mult * request_mult * vip_mult) as i32;
             score.1 += 1;
+
+            spawn_particles(&mut traffic.commands, transform.translation.truncate(), ParticleEmitter::delivery());
+
+            delivered_events.send(ChildDeliveredEvent {
+                child: event.dropped_entity,
+                species: parent.species,
+            });
         }
     }
-}
\ No newline at end of file
+}
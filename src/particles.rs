@@ -0,0 +1,353 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+
+use crate::{animations::{AnimationFrameEvent, AnimationMarker}, hitbox::DropRejected, tween::lerp_color, GameState};
+
+/// Particles are drawn above the children and the queued parents, but below the departing ones.
+const PARTICLES_Z: f32 = 40.0;
+/// How far below the center of a walking sprite its feet are.
+const FOOTSTEP_OFFSET: f32 = 60.0;
+
+pub struct ParticlesPlugin;
+
+#[derive(Clone, Copy, Debug)]
+pub enum EmitterMode {
+    /// Emits all the particles at once and despawns itself.
+    Burst(usize),
+    /// Keeps emitting the given number of particles per second until despawned.
+    Continuous(f32),
+}
+
+/// Spawns sprite particles at its position.
+#[derive(Component, Clone, Debug)]
+pub struct ParticleEmitter {
+    pub mode: EmitterMode,
+    /// Particles are spawned anywhere within this distance from the emitter on each axis.
+    pub area: Vec2,
+    /// Lifetime of a particle in seconds.
+    pub lifetime: f32,
+    /// Initial direction of the particles, randomly turned by up to `spread / 2` either side.
+    pub direction: Vec2,
+    /// Angle in radians, `TAU` means all around.
+    pub spread: f32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    /// Acceleration in pixels per second squared.
+    pub gravity: Vec2,
+    pub start_color: Color,
+    pub end_color: Color,
+    pub start_size: Vec2,
+    pub end_size: Vec2,
+    /// Plain colored rectangles without a texture.
+    pub texture: Option<Handle<Image>>,
+    /// Particles owed by a continuous emitter, to be spawned once they add up to a whole one.
+    accumulator: f32,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        ParticleEmitter {
+            mode: EmitterMode::Burst(12),
+            area: Vec2::ZERO,
+            lifetime: 0.8,
+            direction: Vec2::Y,
+            spread: TAU,
+            min_speed: 50.0,
+            max_speed: 150.0,
+            gravity: Vec2::ZERO,
+            start_color: Color::WHITE,
+            end_color: Color::rgba(1.0, 1.0, 1.0, 0.0),
+            start_size: Vec2::splat(10.0),
+            end_size: Vec2::splat(2.0),
+            texture: None,
+            accumulator: 0.0,
+        }
+    }
+}
+
+impl ParticleEmitter {
+    /// Spores scattering when planted into the garden.
+    pub fn spores() -> Self {
+        ParticleEmitter {
+            mode: EmitterMode::Burst(20),
+            lifetime: 1.2,
+            min_speed: 30.0,
+            max_speed: 120.0,
+            gravity: Vec2::new(0.0, -60.0),
+            start_color: Color::rgb(0.85, 0.75, 0.55),
+            end_color: Color::rgba(0.6, 0.5, 0.35, 0.0),
+            start_size: Vec2::splat(8.0),
+            end_size: Vec2::splat(3.0),
+            ..default()
+        }
+    }
+
+    /// Child grew into the next stage.
+    pub fn sparkles() -> Self {
+        ParticleEmitter {
+            mode: EmitterMode::Burst(16),
+            area: Vec2::new(40.0, 20.0),
+            lifetime: 0.9,
+            spread: TAU / 3.0,
+            min_speed: 80.0,
+            max_speed: 200.0,
+            gravity: Vec2::new(0.0, -150.0),
+            start_color: Color::rgb(1.0, 0.95, 0.5),
+            end_color: Color::rgba(1.0, 1.0, 1.0, 0.0),
+            ..default()
+        }
+    }
+
+    /// Crumbs flying off a fed child.
+    pub fn crumbs() -> Self {
+        ParticleEmitter {
+            mode: EmitterMode::Burst(12),
+            lifetime: 0.6,
+            spread: TAU / 2.0,
+            min_speed: 100.0,
+            max_speed: 220.0,
+            gravity: Vec2::new(0.0, -500.0),
+            start_color: Color::rgb(0.55, 0.35, 0.2),
+            end_color: Color::rgba(0.4, 0.25, 0.15, 0.0),
+            start_size: Vec2::splat(8.0),
+            end_size: Vec2::splat(6.0),
+            ..default()
+        }
+    }
+
+    /// Water splashing around a watered child.
+    pub fn splash() -> Self {
+        ParticleEmitter {
+            mode: EmitterMode::Burst(14),
+            lifetime: 0.6,
+            spread: TAU / 2.0,
+            min_speed: 120.0,
+            max_speed: 260.0,
+            gravity: Vec2::new(0.0, -600.0),
+            start_color: Color::rgba(0.5, 0.7, 1.0, 0.9),
+            end_color: Color::rgba(0.5, 0.7, 1.0, 0.0),
+            start_size: Vec2::splat(9.0),
+            end_size: Vec2::splat(4.0),
+            ..default()
+        }
+    }
+
+    /// Hearts, or at least pink squares, of a parent reunited with their child.
+    pub fn delivery() -> Self {
+        ParticleEmitter {
+            mode: EmitterMode::Burst(24),
+            area: Vec2::new(30.0, 60.0),
+            lifetime: 1.4,
+            spread: TAU / 4.0,
+            min_speed: 60.0,
+            max_speed: 160.0,
+            start_color: Color::rgb(1.0, 0.45, 0.6),
+            end_color: Color::rgba(1.0, 0.7, 0.8, 0.0),
+            start_size: Vec2::splat(12.0),
+            end_size: Vec2::splat(4.0),
+            ..default()
+        }
+    }
+
+    /// Dust kicked up where a drop was rejected.
+    pub fn puff() -> Self {
+        ParticleEmitter {
+            mode: EmitterMode::Burst(10),
+            lifetime: 0.4,
+            min_speed: 60.0,
+            max_speed: 120.0,
+            start_color: Color::rgba(0.8, 0.8, 0.8, 0.8),
+            end_color: Color::rgba(0.8, 0.8, 0.8, 0.0),
+            start_size: Vec2::splat(12.0),
+            end_size: Vec2::splat(20.0),
+            ..default()
+        }
+    }
+
+    /// Dust kicked up by a footstep.
+    pub fn dust() -> Self {
+        ParticleEmitter {
+            mode: EmitterMode::Burst(5),
+            area: Vec2::new(15.0, 0.0),
+            lifetime: 0.5,
+            spread: TAU / 3.0,
+            min_speed: 20.0,
+            max_speed: 60.0,
+            gravity: Vec2::new(0.0, -40.0),
+            start_color: Color::rgba(0.75, 0.65, 0.5, 0.6),
+            end_color: Color::rgba(0.75, 0.65, 0.5, 0.0),
+            start_size: Vec2::splat(8.0),
+            end_size: Vec2::splat(14.0),
+            ..default()
+        }
+    }
+
+    /// Rain falling over the whole screen, from an emitter above it.
+    pub fn rain() -> Self {
+        ParticleEmitter {
+            mode: EmitterMode::Continuous(150.0),
+            area: Vec2::new(crate::WINDOW_WIDTH / 2.0, 0.0),
+            lifetime: 1.4,
+            direction: Vec2::new(0.15, -1.0),
+            spread: 0.0,
+            min_speed: 700.0,
+            max_speed: 900.0,
+            start_color: Color::rgba(0.6, 0.7, 1.0, 0.6),
+            end_color: Color::rgba(0.6, 0.7, 1.0, 0.3),
+            start_size: Vec2::new(3.0, 26.0),
+            end_size: Vec2::new(3.0, 26.0),
+            ..default()
+        }
+    }
+
+    fn emit(&self, commands: &mut Commands, origin: Vec2) {
+        let mut rng = thread_rng();
+
+        let offset = Vec2::new(
+            rng.gen_range(-1.0..=1.0) * self.area.x,
+            rng.gen_range(-1.0..=1.0) * self.area.y,
+        );
+        let angle = rng.gen_range(-0.5..=0.5) * self.spread;
+        let velocity = Vec2::from_angle(angle).rotate(self.direction.normalize_or_zero())
+            * rng.gen_range(self.min_speed..=self.max_speed);
+
+        // falling particles are stretched along their path
+        let rotation = if self.start_size.x == self.start_size.y {
+            Quat::IDENTITY
+        } else {
+            Quat::from_rotation_z(Vec2::Y.angle_between(velocity))
+        };
+
+        commands.spawn((
+            SpriteBundle {
+                texture: self.texture.clone().unwrap_or_default(),
+                transform: Transform::from_translation((origin + offset).extend(PARTICLES_Z)).with_rotation(rotation),
+                sprite: Sprite {
+                    color: self.start_color,
+                    custom_size: Some(self.start_size),
+                    ..default()
+                },
+                ..default()
+            },
+            Particle {
+                velocity,
+                gravity: self.gravity,
+                age: 0.0,
+                lifetime: self.lifetime,
+                start_color: self.start_color,
+                end_color: self.end_color,
+                start_size: self.start_size,
+                end_size: self.end_size,
+            },
+            crate::GameObject,
+        ));
+    }
+}
+
+#[derive(Component, Debug)]
+struct Particle {
+    velocity: Vec2,
+    gravity: Vec2,
+    age: f32,
+    lifetime: f32,
+    start_color: Color,
+    end_color: Color,
+    start_size: Vec2,
+    end_size: Vec2,
+}
+
+/// Spawns an emitter at the given position.
+pub fn spawn_particles(commands: &mut Commands, position: Vec2, emitter: ParticleEmitter) -> Entity {
+    commands.spawn((
+        SpatialBundle::from_transform(Transform::from_translation(position.extend(PARTICLES_Z))),
+        emitter,
+        crate::GameObject,
+    )).id()
+}
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                emit_particles,
+                update_particles,
+                puff_rejected_drops,
+                kick_up_dust,
+            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
+    }
+}
+
+fn emit_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ParticleEmitter, &GlobalTransform)>,
+) {
+    for (entity, mut emitter, transform) in &mut query {
+        let origin = transform.translation().truncate();
+
+        match emitter.mode {
+            EmitterMode::Burst(count) => {
+                for _ in 0..count {
+                    emitter.emit(&mut commands, origin);
+                }
+                commands.entity(entity).despawn_recursive();
+            },
+            EmitterMode::Continuous(rate) => {
+                emitter.accumulator += rate * time.delta_seconds();
+                while emitter.accumulator >= 1.0 {
+                    emitter.accumulator -= 1.0;
+                    emitter.emit(&mut commands, origin);
+                }
+            },
+        }
+    }
+}
+
+fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    let delta = time.delta_seconds();
+
+    for (entity, mut particle, mut transform, mut sprite) in &mut query {
+        particle.age += delta;
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let gravity = particle.gravity;
+        particle.velocity += gravity * delta;
+        transform.translation += (particle.velocity * delta).extend(0.0);
+
+        let t = particle.age / particle.lifetime;
+        sprite.color = lerp_color(particle.start_color, particle.end_color, t);
+        sprite.custom_size = Some(particle.start_size.lerp(particle.end_size, t));
+    }
+}
+
+fn puff_rejected_drops(
+    mut commands: Commands,
+    mut events: EventReader<DropRejected>,
+) {
+    for event in events.read() {
+        spawn_particles(&mut commands, event.position, ParticleEmitter::puff());
+    }
+}
+
+fn kick_up_dust(
+    mut commands: Commands,
+    mut events: EventReader<AnimationFrameEvent>,
+    transform_query: Query<&GlobalTransform>,
+) {
+    for event in events.read() {
+        if event.marker != AnimationMarker::Footstep { continue; }
+        let Ok(transform) = transform_query.get(event.entity) else { continue };
+
+        let feet = transform.translation().truncate() - Vec2::Y * FOOTSTEP_OFFSET;
+        spawn_particles(&mut commands, feet, ParticleEmitter::dust());
+    }
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::menu::click_music_button;
+use crate::{localization::Localization, menu::click_music_button};
 
 #[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
 pub enum PausedState {
@@ -60,6 +60,7 @@ pub enum ButtonAction {
 fn build_pause_menu(
     mut commands: Commands,
     textures: Res<crate::loading::TextureAssets>,
+    localization: Res<Localization>,
 ) {
     // darkness overlay to make the game look darker
     commands.spawn((
@@ -96,7 +97,7 @@ fn build_pause_menu(
             text: Text {
                 sections: vec![
                     TextSection {
-                        value: "Paused".to_string(),
+                        value: localization.text("pause.title"),
                         style: TextStyle {
                             font_size: 80.0,
                             color: Color::WHITE,
@@ -119,7 +120,7 @@ fn build_pause_menu(
             text: Text {
                 sections: vec![
                     TextSection {
-                        value: "Press Escape to unpause".to_string(),
+                        value: localization.text("pause.hint"),
                         style: TextStyle {
                             font_size: 16.0,
                             color: Color::WHITE,
@@ -142,15 +143,7 @@ fn build_pause_menu(
             text: Text {
                 sections: vec![
                     TextSection {
-                        value: (
-                            "Guide:\n".to_string() + 
-                            " Parent shrooms deliver spores.\n" +
-                            " Plant spores by moving them to garden plot.\n" +
-                            " As spores grow, they will start asking for food and water.\n" +
-                            " Both food and water is in bucket on the right. \n" +
-                            " Some kids are special. Beware of their mischief!\n" +
-                            " Once kid is fully grown up deliver back it to the parent."
-                        ).to_string(),
+                        value: localization.text("pause.guide"),
                         style: TextStyle {
                             font_size: 16.0,
                             color: Color::WHITE,
@@ -189,7 +182,7 @@ fn build_pause_menu(
                 text: Text {
                     sections: vec![
                         TextSection {
-                            value: "Resume".to_string(),
+                            value: localization.text("pause.resume"),
                             style: TextStyle {
                                 font_size: 40.0,
                                 color: Color::WHITE,
@@ -229,7 +222,7 @@ fn build_pause_menu(
                 text: Text {
                     sections: vec![
                         TextSection {
-                            value: "Menu".to_string(),
+                            value: localization.text("pause.menu"),
                             style: TextStyle {
                                 font_size: 40.0,
                                 color: Color::WHITE,
@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    achievements::Achievement, child::Child, child_walking::ChildEscapedEvent, growing::{Growable, HypnosisStartedEvent}, localization::Language, needs::{NeedBubbleEvent, Needs}, parents::{ChildDeliveredEvent, Species}, GameState
+};
+
+/// File the profile is stored in, relative to the working directory.
+#[cfg(not(target_arch = "wasm32"))]
+const PROFILE_PATH: &str = "profile.ron";
+
+pub struct ProfilePlugin;
+
+/// Lifetime statistics, kept across game sessions.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Statistics {
+    pub fungi_raised: u32,
+    pub derps_raised: u32,
+    pub psychos_raised: u32,
+    pub posers_raised: u32,
+    /// Longest shift survived, in seconds.
+    pub best_survival_time: f32,
+    pub hypnosis_incidents: u32,
+}
+
+/// Local player profile. Loaded on startup and saved whenever a shift ends or an achievement unlocks.
+#[derive(Resource, Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Profile {
+    pub statistics: Statistics,
+    pub achievements: Vec<Achievement>,
+    pub language: Language,
+}
+
+/// Statistics of the currently running shift. Reset upon entering `GameState::Playing`.
+#[derive(Resource, Debug, Default)]
+pub struct ShiftStatistics {
+    pub survival_time: f32,
+    /// Posers delivered since the last one escaped the garden.
+    pub poser_streak: u32,
+    pub psychos_delivered: u32,
+    /// Time in seconds spent growing children without a single need bubble appearing.
+    pub time_without_bubble: f32,
+}
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(load_profile())
+            .init_resource::<ShiftStatistics>()
+            .add_systems(OnEnter(GameState::Playing), reset_shift_statistics)
+            .add_systems(OnExit(GameState::Playing), finish_shift)
+            .add_systems(Update, (
+                update_shift_time,
+                record_deliveries,
+                record_escapes,
+                record_need_bubbles,
+                record_hypnosis,
+            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_profile() -> Profile {
+    let Ok(contents) = std::fs::read_to_string(PROFILE_PATH) else {
+        return Profile::default();
+    };
+
+    ron::from_str(&contents).unwrap_or_else(|error| {
+        warn!("Failed to parse profile {PROFILE_PATH}: {error}");
+        Profile::default()
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_profile() -> Profile {
+    Profile::default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_profile(profile: &Profile) {
+    let contents = match ron::ser::to_string_pretty(profile, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(error) => {
+            warn!("Failed to serialize profile: {error}");
+            return;
+        }
+    };
+
+    if let Err(error) = std::fs::write(PROFILE_PATH, contents) {
+        warn!("Failed to save profile {PROFILE_PATH}: {error}");
+    }
+}
+
+/// There is no file system on the web, the profile lives only as long as the page.
+#[cfg(target_arch = "wasm32")]
+pub fn save_profile(_profile: &Profile) {}
+
+fn reset_shift_statistics(mut shift: ResMut<ShiftStatistics>) {
+    *shift = ShiftStatistics::default();
+}
+
+fn finish_shift(
+    shift: Res<ShiftStatistics>,
+    mut profile: ResMut<Profile>,
+) {
+    let statistics = &mut profile.statistics;
+    statistics.best_survival_time = statistics.best_survival_time.max(shift.survival_time);
+    save_profile(&profile);
+}
+
+fn update_shift_time(
+    time: Res<Time>,
+    mut shift: ResMut<ShiftStatistics>,
+    growing_query: Query<(), (With<Growable>, With<Needs>)>,
+) {
+    shift.survival_time += time.delta_seconds();
+
+    // an empty garden doesn't count as taking good care of it
+    if !growing_query.is_empty() {
+        shift.time_without_bubble += time.delta_seconds();
+    }
+}
+
+fn record_deliveries(
+    mut events: EventReader<ChildDeliveredEvent>,
+    mut shift: ResMut<ShiftStatistics>,
+    mut profile: ResMut<Profile>,
+) {
+    for event in events.read() {
+        let statistics = &mut profile.statistics;
+        statistics.fungi_raised += 1;
+
+        match event.species {
+            Species::Derp => statistics.derps_raised += 1,
+            Species::Psycho => {
+                statistics.psychos_raised += 1;
+                shift.psychos_delivered += 1;
+            },
+            Species::Poser => {
+                statistics.posers_raised += 1;
+                shift.poser_streak += 1;
+            },
+        }
+    }
+}
+
+fn record_escapes(
+    mut events: EventReader<ChildEscapedEvent>,
+    child_query: Query<&Child>,
+    mut shift: ResMut<ShiftStatistics>,
+) {
+    for event in events.read() {
+        if child_query.get(event.child).is_ok_and(|child| child.species == Species::Poser) {
+            shift.poser_streak = 0;
+        }
+    }
+}
+
+fn record_need_bubbles(
+    mut events: EventReader<NeedBubbleEvent>,
+    mut shift: ResMut<ShiftStatistics>,
+) {
+    if events.read().count() > 0 {
+        shift.time_without_bubble = 0.0;
+    }
+}
+
+fn record_hypnosis(
+    mut events: EventReader<HypnosisStartedEvent>,
+    mut profile: ResMut<Profile>,
+) {
+    profile.statistics.hypnosis_incidents += events.read().count() as u32;
+}
@@ -1,25 +1,31 @@
 use bevy::prelude::*;
 
-use crate::GameState;
+use crate::{tween::{apply_tweens, Ease, Repeat, Tween, TweenCompleted, TweenTarget}, GameState};
+
+/// Tells the pulsing tween apart from other tweens on the same entity.
+const PULSING_TWEEN_ID: u32 = 1;
 
 pub struct PulsingPlugin;
 
 /// Adds pulsing effect (scaling up and down) to an entity.
+/// Drives a [`Tween`] on the entity, which is resumed after any other tween played on it finishes.
 #[derive(Component)]
 #[component(storage = "SparseSet")]
 pub struct Pulsing {
+    /// Change of scale per second.
     pub speed: f32,
     pub min: f32,
     pub max: f32,
-    pub direction: f32,
 }
 
 impl Plugin for PulsingPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(Update, (
-                apply_pulse
-            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
+                start_pulsing,
+                resume_pulsing,
+                stop_pulsing,
+            ).before(apply_tweens).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
     }
 }
 
@@ -29,26 +35,55 @@ impl Default for Pulsing {
             speed: 1.0,
             min: 0.8,
             max: 1.2,
-            direction: 1.0,
         }   
     }
 }
 
-fn apply_pulse(
-    time: Res<Time>,
-    mut query: Query<(&mut Transform, &mut Pulsing)>,
+impl Pulsing {
+    /// Goes from the current scale up to max, down to min and back, keeping the z scale.
+    fn tween(&self, scale: Vec3) -> Tween {
+        let rest = scale.truncate().clamp(Vec2::splat(self.min), Vec2::splat(self.max)).extend(scale.z);
+        let max = Vec2::splat(self.max).extend(scale.z);
+        let min = Vec2::splat(self.min).extend(scale.z);
+
+        Tween::new(TweenTarget::Scale { from: rest, to: max }, (self.max - rest.x) / self.speed, Ease::QuadOut)
+            .then(TweenTarget::Scale { from: max, to: min }, (self.max - self.min) / self.speed, Ease::SineInOut)
+            .then(TweenTarget::Scale { from: min, to: rest }, (rest.x - self.min) / self.speed, Ease::QuadIn)
+            .with_repeat(Repeat::Forever)
+            .with_id(PULSING_TWEEN_ID)
+    }
+}
+
+fn start_pulsing(
+    mut commands: Commands,
+    query: Query<(Entity, &Pulsing, &Transform), Changed<Pulsing>>,
 ) {
-    for (mut transform, mut pulsing) in query.iter_mut() {
-        transform.scale += Vec2::splat(pulsing.direction * time.delta_seconds() * pulsing.speed).extend(0.0);
+    for (entity, pulsing, transform) in &query {
+        commands.entity(entity).insert(pulsing.tween(transform.scale));
+    }
+}
 
-        if transform.scale.x > pulsing.max {
-            transform.scale = Vec2::splat(pulsing.max).extend(1.0);
-            pulsing.direction = -1.0;
-        }
+/// Other tweens take over the scale for a while, pulsing goes on once they are done.
+fn resume_pulsing(
+    mut commands: Commands,
+    mut events: EventReader<TweenCompleted>,
+    query: Query<(&Pulsing, &Transform)>,
+) {
+    for event in events.read() {
+        let Ok((pulsing, transform)) = query.get(event.entity) else { continue };
+        commands.entity(event.entity).insert(pulsing.tween(transform.scale));
+    }
+}
 
-        if transform.scale.x < pulsing.min {
-            transform.scale = Vec2::splat(pulsing.min).extend(1.0);
-            pulsing.direction = 1.0;
+fn stop_pulsing(
+    mut commands: Commands,
+    mut removed: RemovedComponents<Pulsing>,
+    tweens: Query<&Tween>,
+) {
+    for entity in removed.read() {
+        let Ok(tween) = tweens.get(entity) else { continue };
+        if tween.id == PULSING_TWEEN_ID {
+            commands.entity(entity).remove::<Tween>();
         }
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::localization::Localization;
+
 #[derive(Resource, Clone, Debug, Default)]
 pub struct Score(pub i32, pub i32);
 
@@ -25,13 +27,14 @@ struct ScoreText;
 
 fn setup_score_ui(
     mut commands: Commands,
+    localization: Res<Localization>,
 ) {
     commands.spawn((
         TextBundle {
             text: Text {
                 sections: vec![
                     TextSection {
-                        value: "Score: 0".to_string(),
+                        value: localization.format("hud.score", &[("score", &0)]),
                         style: TextStyle {
                             font_size: 40.0,
                             color: Color::WHITE,
@@ -53,9 +56,9 @@ fn setup_score_ui(
     ));
 }
 
-fn update_score_ui(score: Res<Score>, mut query: Query<&mut Text, With<ScoreText>>) {
+fn update_score_ui(score: Res<Score>, localization: Res<Localization>, mut query: Query<&mut Text, With<ScoreText>>) {
     for mut text in &mut query {
-        text.sections[0].value = format!("Score: {}", score.0);
+        text.sections[0].value = localization.format("hud.score", &[("score", &score.0)]);
     }
 }
 
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+
+use crate::{child::Child, growing::{add_hypnotic_behaviour, Growable, HypnoBehaviour, HypnoDespawnEvent}, hitbox::*, loading::{AnimationAssets, TextureAssets}, GameState};
+
+const SLEEP_MASK_SPAWN_POS: Vec2 = Vec2::new(750.0, -430.0);
+const SLEEP_MASK_SIZE: Vec2 = Vec2::splat(128.0);
+const HITBOX_SIZE: Vec2 = Vec2::splat(128.0);
+
+/// Where the mask sits on the face of the child wearing it.
+const WORN_MASK_OFFSET: Vec3 = Vec3::new(0.0, 70.0, 6.0);
+const WORN_MASK_SIZE: Vec2 = Vec2::new(100.0, 100.0);
+
+/// How long a psycho child sleeps before it starts hypnotizing again.
+const SUPPRESSION_DURATION: f32 = 10.0;
+
+pub struct SleepMaskPlugin;
+
+/// Tool which puts a psycho child to sleep for a while.
+#[derive(Component, Default)]
+pub struct SleepMask {
+    worn_by: Option<MaskWearer>,
+}
+
+struct MaskWearer {
+    child: Entity,
+    worn_mask: Entity,
+    timer: Timer,
+}
+
+impl Plugin for SleepMaskPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::Playing), spawn_sleep_mask)
+            .add_systems(Update, (
+                read_on_drop_events,
+                wake_up_psychos,
+            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
+    }
+}
+
+fn sleep_mask_draggable() -> Draggable {
+    Draggable {
+        must_intersect_with: Some(Layer::Child.into()),
+        drag_opaque: true,
+        ..default()
+    }
+}
+
+fn spawn_sleep_mask(mut commands: Commands, textures: Res<TextureAssets>) {
+    commands.spawn((
+        SpriteBundle {
+            texture: textures.sleep_mask.clone(),
+            transform: Transform::from_translation(SLEEP_MASK_SPAWN_POS.extend(1.0)),
+            sprite: Sprite {
+                custom_size: Some(SLEEP_MASK_SIZE),
+                ..default()
+            },
+            ..default()
+        },
+        Hitbox::new_centered(HITBOX_SIZE),
+        InLayers::new_single(Layer::Tool),
+        sleep_mask_draggable(),
+        SleepMask::default(),
+        crate::GameObject,
+    ));
+}
+
+fn read_on_drop_events(
+    mut commands: Commands,
+    mut events: EventReader<DropEvent>,
+    textures: Res<TextureAssets>,
+    mut mask_query: Query<(&mut SleepMask, &mut Transform, &mut Visibility)>,
+    child_query: Query<&Children, With<Child>>,
+    hypno_query: Query<(), With<HypnoBehaviour>>,
+    mut hypno_despawn_events: EventWriter<HypnoDespawnEvent>,
+) {
+    for event in events.read() {
+        let Ok((mut mask, mut transform, mut visibility)) = mask_query.get_mut(event.dropped_entity) else { continue };
+
+        transform.translation = SLEEP_MASK_SPAWN_POS.extend(1.0);
+
+        // only hypnotizing children need some sleep
+        let Ok(children) = child_query.get(event.dropped_on_entity) else { continue };
+        if !children.iter().any(|child| hypno_query.contains(*child)) { continue; }
+
+        hypno_despawn_events.send(HypnoDespawnEvent { parent: event.dropped_on_entity });
+
+        let worn_mask = commands.spawn(SpriteBundle {
+            texture: textures.sleep_mask.clone(),
+            transform: Transform::from_translation(WORN_MASK_OFFSET),
+            sprite: Sprite {
+                custom_size: Some(WORN_MASK_SIZE),
+                ..default()
+            },
+            ..default()
+        }).id();
+        commands.entity(event.dropped_on_entity).add_child(worn_mask);
+
+        // the mask is on the child's face until it wakes up
+        *visibility = Visibility::Hidden;
+        commands.entity(event.dropped_entity).remove::<Draggable>();
+
+        mask.worn_by = Some(MaskWearer {
+            child: event.dropped_on_entity,
+            worn_mask,
+            timer: Timer::from_seconds(SUPPRESSION_DURATION, TimerMode::Once),
+        });
+    }
+}
+
+fn wake_up_psychos(
+    mut commands: Commands,
+    time: Res<Time>,
+    animation_assets: Res<AnimationAssets>,
+    mut mask_query: Query<(Entity, &mut SleepMask, &mut Visibility, &mut Sprite)>,
+    child_query: Query<(), (With<Child>, With<Growable>)>,
+) {
+    for (entity, mut mask, mut visibility, mut sprite) in &mut mask_query {
+        let Some(wearer) = mask.worn_by.as_mut() else { continue };
+
+        // the child might have been delivered in its sleep
+        let still_here = child_query.contains(wearer.child);
+
+        wearer.timer.tick(time.delta());
+        if still_here && !wearer.timer.finished() { continue; }
+
+        if let Some(worn_mask) = commands.get_entity(wearer.worn_mask) {
+            worn_mask.despawn_recursive();
+        }
+
+        if still_here {
+            add_hypnotic_behaviour(&mut commands, wearer.child, &animation_assets);
+        }
+
+        mask.worn_by = None;
+        *visibility = Visibility::Inherited;
+        commands.entity(entity).insert(sleep_mask_draggable());
+
+        // the mask might have lost its hover shadow together with the draggable
+        sprite.color.set_a(1.0);
+    }
+}
@@ -0,0 +1,142 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{child::Child, growing::Growable, loading::AnimationAssets, parents::{ChildDeliveredEvent, Species}, GameState};
+
+pub struct SpeciesPlugin;
+
+/// Everything a species behaviour hook gets to work with.
+pub struct BehaviourContext<'a, 'w, 's> {
+    pub commands: &'a mut Commands<'w, 's>,
+    pub animation_assets: &'a AnimationAssets,
+    /// The child the hook is called for.
+    /// On delivery the child may already be despawned, so use `commands.get_entity`.
+    pub child: Entity,
+}
+
+/// Special behaviour of a species, hooked into the life of its children.
+/// Register with [`SpeciesBehaviourAppExt::register_species_behaviour`].
+pub trait SpeciesBehaviour: Send + Sync + 'static {
+    /// Spores were planted into the garden.
+    fn on_plant(&self, _context: &mut BehaviourContext) {}
+    /// Child grew into the next stage. Also called when reaching maturity.
+    fn on_stage_change(&self, _context: &mut BehaviourContext, _stage: usize) {}
+    /// Child is fully grown and can be delivered.
+    fn on_maturity(&self, _context: &mut BehaviourContext) {}
+    /// Child was handed back to its parent.
+    fn on_delivery(&self, _context: &mut BehaviourContext) {}
+    /// Called every frame for each planted child.
+    fn on_update(&self, _context: &mut BehaviourContext, _delta: f32) {}
+}
+
+#[derive(Resource, Default)]
+pub struct SpeciesBehaviours(HashMap<Species, Vec<Box<dyn SpeciesBehaviour>>>);
+
+impl SpeciesBehaviours {
+    fn of(&self, species: Species) -> &[Box<dyn SpeciesBehaviour>] {
+        self.0.get(&species).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+pub trait SpeciesBehaviourAppExt {
+    fn register_species_behaviour(&mut self, species: Species, behaviour: impl SpeciesBehaviour) -> &mut Self;
+}
+
+impl SpeciesBehaviourAppExt for App {
+    fn register_species_behaviour(&mut self, species: Species, behaviour: impl SpeciesBehaviour) -> &mut Self {
+        self.world.get_resource_or_insert_with(SpeciesBehaviours::default)
+            .0.entry(species)
+            .or_default()
+            .push(Box::new(behaviour));
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum GrowthStep {
+    Planted,
+    StageChanged(usize),
+    Matured,
+}
+
+/// Sent by the growing systems whenever a child moves along in its life.
+#[derive(Event, Debug)]
+pub struct ChildGrowthEvent {
+    pub child: Entity,
+    pub species: Species,
+    pub step: GrowthStep,
+}
+
+impl Plugin for SpeciesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SpeciesBehaviours>()
+            .add_event::<ChildGrowthEvent>()
+            .add_systems(Update, (
+                dispatch_growth_hooks,
+                dispatch_delivery_hooks,
+                dispatch_update_hooks,
+            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
+    }
+}
+
+fn dispatch_growth_hooks(
+    mut commands: Commands,
+    mut events: EventReader<ChildGrowthEvent>,
+    behaviours: Res<SpeciesBehaviours>,
+    animation_assets: Res<AnimationAssets>,
+) {
+    for event in events.read() {
+        let mut context = BehaviourContext {
+            commands: &mut commands,
+            animation_assets: &animation_assets,
+            child: event.child,
+        };
+
+        for behaviour in behaviours.of(event.species) {
+            match event.step {
+                GrowthStep::Planted => behaviour.on_plant(&mut context),
+                GrowthStep::StageChanged(stage) => behaviour.on_stage_change(&mut context, stage),
+                GrowthStep::Matured => behaviour.on_maturity(&mut context),
+            }
+        }
+    }
+}
+
+fn dispatch_delivery_hooks(
+    mut commands: Commands,
+    mut events: EventReader<ChildDeliveredEvent>,
+    behaviours: Res<SpeciesBehaviours>,
+    animation_assets: Res<AnimationAssets>,
+) {
+    for event in events.read() {
+        let mut context = BehaviourContext {
+            commands: &mut commands,
+            animation_assets: &animation_assets,
+            child: event.child,
+        };
+
+        for behaviour in behaviours.of(event.species) {
+            behaviour.on_delivery(&mut context);
+        }
+    }
+}
+
+fn dispatch_update_hooks(
+    mut commands: Commands,
+    time: Res<Time>,
+    behaviours: Res<SpeciesBehaviours>,
+    animation_assets: Res<AnimationAssets>,
+    query: Query<(Entity, &Child), With<Growable>>,
+) {
+    for (entity, child) in &query {
+        let mut context = BehaviourContext {
+            commands: &mut commands,
+            animation_assets: &animation_assets,
+            child: entity,
+        };
+
+        for behaviour in behaviours.of(child.species) {
+            behaviour.on_update(&mut context, time.delta_seconds());
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+
+use crate::{hitbox::*, loading::TextureAssets, parents::Parent, tuning::Tuning, tween::{Ease, Tween, TweenTarget}, GameState};
+
+const TEA_CUP_SIZE: Vec2 = Vec2::splat(112.0);
+const HITBOX_SIZE: Vec2 = Vec2::splat(112.0);
+
+/// Fraction of the whole patience a cup of tea gives back.
+const PATIENCE_RESTORED: f32 = 0.35;
+/// How long it takes to brew another cup.
+const COOLDOWN_DURATION: f32 = 20.0;
+const COOLDOWN_COLOR: Color = Color::rgba(0.5, 0.5, 0.5, 0.5);
+
+pub struct TeaCupPlugin;
+
+/// Tool which calms down a waiting parent.
+#[derive(Component, Default)]
+pub struct TeaCup {
+    cooldown: Option<Timer>,
+}
+
+impl Plugin for TeaCupPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::Playing), spawn_tea_cup)
+            .add_systems(Update, (
+                read_on_drop_events,
+                brew_tea,
+                apply_tuning_to_tea_cup,
+            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
+    }
+}
+
+fn tea_cup_draggable() -> Draggable {
+    Draggable {
+        must_intersect_with: Some(Layer::Parent.into()),
+        drag_opaque: true,
+        ..default()
+    }
+}
+
+fn spawn_tea_cup(mut commands: Commands, textures: Res<TextureAssets>, tuning: Res<Tuning>) {
+    commands.spawn((
+        SpriteBundle {
+            texture: textures.tea_cup.clone(),
+            transform: Transform::from_translation(Vec2::from(tuning.tea_cup_pos).extend(1.0)),
+            sprite: Sprite {
+                custom_size: Some(TEA_CUP_SIZE),
+                ..default()
+            },
+            ..default()
+        },
+        Hitbox::new_centered(HITBOX_SIZE),
+        InLayers::new_single(Layer::Tool),
+        tea_cup_draggable(),
+        TeaCup::default(),
+        crate::GameObject,
+    ));
+}
+
+fn read_on_drop_events(
+    mut commands: Commands,
+    mut events: EventReader<DropEvent>,
+    mut tea_cup_query: Query<(&mut TeaCup, &mut Transform)>,
+    mut parent_query: Query<&mut Parent>,
+    tuning: Res<Tuning>,
+) {
+    let spawn_pos = Vec2::from(tuning.tea_cup_pos).extend(1.0);
+
+    for event in events.read() {
+        let Ok((mut tea_cup, mut transform)) = tea_cup_query.get_mut(event.dropped_entity) else { continue };
+
+        let Ok(mut parent) = parent_query.get_mut(event.dropped_on_entity) else {
+            transform.translation = spawn_pos;
+            continue;
+        };
+        parent.restore_patience(PATIENCE_RESTORED);
+
+        // the empty cup flies back and goes grey until the next one is brewed
+        tea_cup.cooldown = Some(Timer::from_seconds(COOLDOWN_DURATION, TimerMode::Once));
+        commands.entity(event.dropped_entity)
+            .remove::<Draggable>()
+            .insert(
+                Tween::new(TweenTarget::Translation { from: transform.translation, to: spawn_pos }, 0.3, Ease::QuadOut)
+                    .then(TweenTarget::SpriteColor { from: Color::WHITE, to: COOLDOWN_COLOR }, 0.3, Ease::Linear)
+                    .with_delay(0.1)
+            );
+    }
+}
+
+fn brew_tea(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tea_cup_query: Query<(Entity, &mut TeaCup)>,
+) {
+    for (entity, mut tea_cup) in &mut tea_cup_query {
+        let Some(cooldown) = tea_cup.cooldown.as_mut() else { continue };
+
+        cooldown.tick(time.delta());
+        if !cooldown.finished() { continue; }
+
+        tea_cup.cooldown = None;
+        commands.entity(entity).insert((
+            tea_cup_draggable(),
+            Tween::new(TweenTarget::SpriteColor { from: COOLDOWN_COLOR, to: Color::WHITE }, 0.5, Ease::QuadInOut),
+        ));
+    }
+}
+
+/// Moves the cup when the tuning changes, unless it is on its way back.
+fn apply_tuning_to_tea_cup(
+    tuning: Res<Tuning>,
+    mut tea_cup_query: Query<&mut Transform, (With<TeaCup>, Without<Tween>)>,
+) {
+    if !tuning.is_changed() { return; }
+
+    for mut transform in &mut tea_cup_query {
+        transform.translation = Vec2::from(tuning.tea_cup_pos).extend(1.0);
+    }
+}
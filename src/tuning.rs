@@ -0,0 +1,180 @@
+use bevy::{asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext}, prelude::*, utils::BoxedFuture};
+use serde::Deserialize;
+
+use crate::{difficulty::{START_PARENT_SPAWN_TIME, START_PATIENCE}, loading::ConfigAssets, GameState};
+
+pub struct TuningPlugin;
+
+/// Gameplay values loaded from `assets/config/gameplay.tuning.ron`.
+/// With the `dev` feature the file is watched, and saving it applies the new values to the running game.
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Tuning {
+    /// Time which takes to scale difficulties from start to end values.
+    pub difficulty_scaling_time: f32,
+    pub start_patience: f32,
+    pub end_patience: f32,
+    pub start_parent_spawn_time: f32,
+    pub end_parent_spawn_time: f32,
+
+    /// Hunger lost per second.
+    pub hunger_rate: f32,
+    pub hunger_full: f32,
+    /// Thirst lost per second.
+    pub thirst_rate: f32,
+    pub thirst_full: f32,
+
+    pub grow_speed: f32,
+    /// Seconds a child spends in each stage, at grow speed 1.
+    pub grow_duration: f32,
+
+    /// Only children spawned after a change get the new size.
+    pub child_hitbox_size: f32,
+    pub tool_hitbox_size: f32,
+
+    pub food_source_pos: [f32; 2],
+    pub water_source_pos: [f32; 2],
+    pub tea_cup_pos: [f32; 2],
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Tuning {
+            difficulty_scaling_time: 5.0 * 60.0,
+            start_patience: START_PATIENCE,
+            end_patience: 35.0,
+            start_parent_spawn_time: START_PARENT_SPAWN_TIME,
+            end_parent_spawn_time: 5.0,
+
+            hunger_rate: 1.0,
+            hunger_full: 15.0,
+            thirst_rate: 1.0,
+            thirst_full: 10.0,
+
+            grow_speed: 1.0,
+            grow_duration: 5.0,
+
+            child_hitbox_size: 90.0,
+            tool_hitbox_size: 128.0,
+
+            food_source_pos: [750.0, 90.0],
+            water_source_pos: [750.0, -250.0],
+            tea_cup_pos: [880.0, -90.0],
+        }
+    }
+}
+
+impl Tuning {
+    /// Catches values which would break the game, so a typo while editing the file live only fails the reload.
+    fn validate(&self) -> Result<(), String> {
+        let positive = [
+            ("difficulty_scaling_time", self.difficulty_scaling_time),
+            ("start_patience", self.start_patience),
+            ("end_patience", self.end_patience),
+            ("start_parent_spawn_time", self.start_parent_spawn_time),
+            ("end_parent_spawn_time", self.end_parent_spawn_time),
+            ("hunger_full", self.hunger_full),
+            ("thirst_full", self.thirst_full),
+            ("grow_duration", self.grow_duration),
+            ("child_hitbox_size", self.child_hitbox_size),
+            ("tool_hitbox_size", self.tool_hitbox_size),
+        ];
+        for (name, value) in positive {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(format!("{} must be positive, got {}", name, value));
+            }
+        }
+
+        let non_negative = [
+            ("hunger_rate", self.hunger_rate),
+            ("thirst_rate", self.thirst_rate),
+            ("grow_speed", self.grow_speed),
+        ];
+        for (name, value) in non_negative {
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!("{} must not be negative, got {}", name, value));
+            }
+        }
+
+        let positions = [
+            ("food_source_pos", self.food_source_pos),
+            ("water_source_pos", self.water_source_pos),
+            ("tea_cup_pos", self.tea_cup_pos),
+        ];
+        for (name, position) in positions {
+            if !position.iter().all(|coord| coord.is_finite()) {
+                return Err(format!("{} must be finite, got {:?}", name, position));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct TuningLoader;
+
+impl AssetLoader for TuningLoader {
+    type Asset = Tuning;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Tuning, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let tuning: Tuning = ron::de::from_bytes(&bytes)?;
+            tuning.validate()?;
+            Ok(tuning)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tuning.ron"]
+    }
+}
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<Tuning>()
+            .init_asset_loader::<TuningLoader>()
+            .init_resource::<Tuning>()
+            .add_systems(OnExit(GameState::Loading), init_tuning)
+            .add_systems(Update, apply_tuning.run_if(resource_exists::<ConfigAssets>));
+    }
+}
+
+/// The file is loaded together with the other assets, so the first shift already starts with its values.
+fn init_tuning(
+    config: Res<ConfigAssets>,
+    assets: Res<Assets<Tuning>>,
+    mut tuning: ResMut<Tuning>,
+) {
+    match assets.get(&config.tuning) {
+        Some(loaded) => *tuning = loaded.clone(),
+        None => error!("tuning is not loaded"),
+    }
+}
+
+/// Copies the file into the resource again whenever it changes.
+/// A change with invalid values fails to load, and the previous values stay.
+fn apply_tuning(
+    mut events: EventReader<AssetEvent<Tuning>>,
+    config: Res<ConfigAssets>,
+    assets: Res<Assets<Tuning>>,
+    mut tuning: ResMut<Tuning>,
+) {
+    for event in events.read() {
+        if !event.is_loaded_with_dependencies(&config.tuning) && !event.is_modified(&config.tuning) { continue; }
+        let Some(loaded) = assets.get(&config.tuning) else { continue };
+
+        info!("tuning applied");
+        *tuning = loaded.clone();
+    }
+}
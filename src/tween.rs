@@ -0,0 +1,260 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::GameState;
+
+pub struct TweenPlugin;
+
+/// Easing functions, mapping linear progress 0 to 1 onto eased progress.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Ease {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    SineInOut,
+    /// Overshoots a little and settles back.
+    BackOut,
+    /// Wobbles around the end value before settling.
+    ElasticOut,
+}
+
+impl Ease {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Ease::Linear => t,
+            Ease::QuadIn => t * t,
+            Ease::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Ease::QuadInOut => if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0 },
+            Ease::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+            Ease::BackOut => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.0;
+                1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+            },
+            Ease::ElasticOut => {
+                if t == 0.0 || t == 1.0 { return t; }
+                2.0_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+            },
+        }
+    }
+}
+
+/// What a tween step animates.
+#[derive(Clone, Copy, Debug)]
+pub enum TweenTarget {
+    Translation { from: Vec3, to: Vec3 },
+    /// Moves along a parabola, rising `height` above the straight line halfway through.
+    Arc { from: Vec3, to: Vec3, height: f32 },
+    Scale { from: Vec3, to: Vec3 },
+    Rotation { from: Quat, to: Quat },
+    SpriteColor { from: Color, to: Color },
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Repeat {
+    Once,
+    /// Plays the whole tween the given number of times.
+    Times(u32),
+    Forever,
+}
+
+/// Single part of a tween sequence.
+#[derive(Clone, Debug)]
+pub struct TweenStep {
+    pub target: TweenTarget,
+    /// Length of the step in seconds, not counting the delay.
+    pub duration: f32,
+    pub ease: Ease,
+    /// Seconds to wait before the step starts.
+    pub delay: f32,
+}
+
+/// Animates the transform or sprite color of an entity through a sequence of steps.
+/// The component is removed and a [`TweenCompleted`] event is sent once it finishes.
+#[derive(Component, Clone, Debug)]
+#[component(storage = "SparseSet")]
+pub struct Tween {
+    steps: Vec<TweenStep>,
+    pub repeat: Repeat,
+    /// Plays the sequence backwards after each forward run. A forward and backward run count as one.
+    pub yoyo: bool,
+    /// Tells tweens apart, so that a system only stops the tweens it started.
+    pub id: u32,
+
+    current: usize,
+    elapsed: f32,
+    backwards: bool,
+    loops_done: u32,
+}
+
+/// Sent when a tween finished all its repeats.
+#[derive(Event, Debug)]
+pub struct TweenCompleted {
+    pub entity: Entity,
+}
+
+impl Tween {
+    pub fn new(target: TweenTarget, duration: f32, ease: Ease) -> Self {
+        Tween {
+            steps: vec![TweenStep { target, duration, ease, delay: 0.0 }],
+            repeat: Repeat::Once,
+            yoyo: false,
+            id: 0,
+            current: 0,
+            elapsed: 0.0,
+            backwards: false,
+            loops_done: 0,
+        }
+    }
+
+    /// Appends another step to the sequence.
+    pub fn then(mut self, target: TweenTarget, duration: f32, ease: Ease) -> Self {
+        self.steps.push(TweenStep { target, duration, ease, delay: 0.0 });
+        self
+    }
+
+    /// Delays the last added step.
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        if let Some(step) = self.steps.last_mut() {
+            step.delay = delay;
+        }
+        self
+    }
+
+    pub fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn with_yoyo(mut self) -> Self {
+        self.yoyo = true;
+        self
+    }
+
+    pub fn with_id(mut self, id: u32) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Squashes the scale and lets it spring back, starting from the given scale.
+    pub fn squash(scale: Vec3, amount: f32) -> Self {
+        let squashed = scale * Vec3::new(1.0 + amount, 1.0 - amount, 1.0);
+        Tween::new(TweenTarget::Scale { from: scale, to: squashed }, 0.08, Ease::QuadOut)
+            .then(TweenTarget::Scale { from: squashed, to: scale }, 0.4, Ease::ElasticOut)
+    }
+
+    /// Turns left and right around the given rotation, like shaking a head.
+    pub fn shake(rotation: Quat, angle: f32) -> Self {
+        let left = rotation * Quat::from_rotation_z(angle);
+        let right = rotation * Quat::from_rotation_z(-angle);
+        Tween::new(TweenTarget::Rotation { from: rotation, to: left }, 0.05, Ease::QuadOut)
+            .then(TweenTarget::Rotation { from: left, to: right }, 0.1, Ease::SineInOut)
+            .then(TweenTarget::Rotation { from: right, to: rotation }, 0.05, Ease::QuadIn)
+            .with_repeat(Repeat::Times(2))
+    }
+
+    /// Moves the tween along, returns `true` once it is finished.
+    fn advance(&mut self, delta: f32) -> bool {
+        self.elapsed += delta;
+
+        // nothing to wait for, would loop forever
+        if self.steps.iter().all(|step| step.delay + step.duration <= 0.0) {
+            return self.repeat != Repeat::Forever;
+        }
+
+        loop {
+            let step = &self.steps[self.current];
+            let length = step.delay + step.duration;
+            if self.elapsed < length {
+                return false;
+            }
+            self.elapsed -= length;
+
+            let last = if self.backwards { self.current == 0 } else { self.current == self.steps.len() - 1 };
+            if !last {
+                if self.backwards { self.current -= 1; } else { self.current += 1; }
+                continue;
+            }
+
+            if self.yoyo && !self.backwards {
+                self.backwards = true;
+                continue;
+            }
+
+            self.loops_done += 1;
+            let finished = match self.repeat {
+                Repeat::Once => true,
+                Repeat::Times(times) => self.loops_done >= times,
+                Repeat::Forever => false,
+            };
+            if finished {
+                self.elapsed = length;
+                return true;
+            }
+
+            self.backwards = false;
+            self.current = 0;
+        }
+    }
+
+    /// Eased position within the current step, 0 at its start value and 1 at its end value.
+    fn progress(&self) -> f32 {
+        let step = &self.steps[self.current];
+        let t = if step.duration > 0.0 {
+            (self.elapsed - step.delay).max(0.0) / step.duration
+        } else {
+            1.0
+        };
+
+        // going backwards, the step is played from its end to its start
+        if self.backwards {
+            step.ease.apply(1.0 - t)
+        } else {
+            step.ease.apply(t)
+        }
+    }
+}
+
+pub fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    Color::rgba_from_array(Vec4::from_array(from.as_rgba_f32()).lerp(Vec4::from_array(to.as_rgba_f32()), t))
+}
+
+impl Plugin for TweenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<TweenCompleted>()
+            .add_systems(Update, (
+                apply_tweens,
+            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))));
+    }
+}
+
+pub fn apply_tweens(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Tween, &mut Transform, Option<&mut Sprite>)>,
+    mut completed_events: EventWriter<TweenCompleted>,
+) {
+    for (entity, mut tween, mut transform, sprite) in &mut query {
+        let finished = tween.advance(time.delta_seconds());
+
+        let t = tween.progress();
+        match tween.steps[tween.current].target {
+            TweenTarget::Translation { from, to } => transform.translation = from.lerp(to, t),
+            TweenTarget::Arc { from, to, height } => transform.translation = from.lerp(to, t) + Vec3::Y * height * 4.0 * t * (1.0 - t),
+            TweenTarget::Scale { from, to } => transform.scale = from.lerp(to, t),
+            TweenTarget::Rotation { from, to } => transform.rotation = from.slerp(to, t),
+            TweenTarget::SpriteColor { from, to } => if let Some(mut sprite) = sprite {
+                sprite.color = lerp_color(from, to, t);
+            },
+        }
+
+        if finished {
+            commands.entity(entity).remove::<Tween>();
+            completed_events.send(TweenCompleted { entity });
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use bevy::{asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext}, prelude::*, utils::BoxedFuture};
+use serde::Deserialize;
+
+use crate::{difficulty::Difficulty, loading::ConfigAssets, localization::Localization, parents::Parent, GameState};
+
+/// How many seconds before a rush the warning shows up.
+const RUSH_WARNING_TIME: f32 = 8.0;
+const RUSH_WARNING_COLOR: Color = Color::rgb(1.0, 0.85, 0.3);
+
+pub struct WavesPlugin;
+
+/// How often parents come during a wave.
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum SpawnPace {
+    /// Multiplies the spawn time given by the difficulty.
+    Relative(f32),
+    /// A parent every given number of seconds.
+    Fixed(f32),
+    /// Nobody comes.
+    Closed,
+}
+
+/// Part of the day with its own pace of arriving parents.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Wave {
+    /// Key of the name in the string tables.
+    pub name: String,
+    /// Length of the wave in seconds.
+    pub duration: f32,
+    pub pace: SpawnPace,
+    /// Rushes are announced in advance.
+    #[serde(default)]
+    pub rush: bool,
+}
+
+/// Schedule of waves, which decides how fast the parents spawn.
+/// Loaded from the `*.timeline.ron` files in `assets/config`.
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, Default, Debug)]
+pub struct WaveTimeline {
+    waves: Vec<Wave>,
+    /// Endless timelines start over after the last wave, others are over.
+    #[serde(default)]
+    looping: bool,
+    #[serde(skip)]
+    current: usize,
+    /// Time spent in the current wave.
+    #[serde(skip)]
+    elapsed: f32,
+}
+
+impl WaveTimeline {
+    /// `None` once a non-looping timeline is over.
+    pub fn current(&self) -> Option<&Wave> {
+        self.waves.get(self.current)
+    }
+
+    fn upcoming(&self) -> Option<&Wave> {
+        match self.waves.get(self.current + 1) {
+            Some(wave) => Some(wave),
+            None if self.looping => self.waves.first(),
+            None => None,
+        }
+    }
+
+    fn time_left(&self) -> f32 {
+        self.current().map_or(0.0, |wave| wave.duration - self.elapsed)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// Seconds between two parents, `None` when nobody should come.
+    pub fn spawn_interval(&self, difficulty: &Difficulty) -> Option<f32> {
+        match self.current()?.pace {
+            SpawnPace::Relative(mult) => Some(difficulty.parent_spawn_time * mult),
+            SpawnPace::Fixed(interval) => Some(interval),
+            SpawnPace::Closed => None,
+        }
+    }
+
+    /// Catches values which would break the spawning, so a typo fails the load instead of the game.
+    fn validate(&self) -> Result<(), String> {
+        for wave in &self.waves {
+            if !wave.duration.is_finite() || wave.duration < 0.0 {
+                return Err(format!("wave {} has invalid duration {}", wave.name, wave.duration));
+            }
+
+            if let SpawnPace::Relative(pace) | SpawnPace::Fixed(pace) = wave.pace {
+                if !pace.is_finite() || pace <= 0.0 {
+                    return Err(format!("wave {} has invalid pace {:?}", wave.name, wave.pace));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn advance(&mut self, delta: f32) {
+        if self.waves.is_empty() { return; }
+
+        // nothing to wait for, would loop forever
+        if self.looping && self.waves.iter().all(|wave| wave.duration <= 0.0) { return; }
+
+        self.elapsed += delta;
+        while let Some(wave) = self.current() {
+            if self.elapsed < wave.duration { break; }
+
+            self.elapsed -= wave.duration;
+            self.current += 1;
+            if self.looping && self.current >= self.waves.len() {
+                self.current = 0;
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct WaveTimelineLoader;
+
+impl AssetLoader for WaveTimelineLoader {
+    type Asset = WaveTimeline;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<WaveTimeline, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let timeline: WaveTimeline = ron::de::from_bytes(&bytes)?;
+            timeline.validate()?;
+            Ok(timeline)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["timeline.ron"]
+    }
+}
+
+/// Which timeline is played.
+#[derive(Resource, Default, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ShiftMode {
+    #[default]
+    Endless,
+    Workday,
+}
+
+impl ShiftMode {
+    /// Key of the button label in the string tables.
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            ShiftMode::Endless => "menu.mode_endless",
+            ShiftMode::Workday => "menu.mode_workday",
+        }
+    }
+
+    fn timeline<'a>(&self, config: &'a ConfigAssets) -> &'a Handle<WaveTimeline> {
+        match self {
+            ShiftMode::Endless => &config.endless_timeline,
+            ShiftMode::Workday => &config.workday_timeline,
+        }
+    }
+}
+
+/// Button which switches the shift mode.
+#[derive(Component, Debug)]
+pub struct ToggleShiftMode;
+
+#[derive(Component, Debug)]
+struct RushWarningText;
+
+impl Plugin for WavesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<WaveTimeline>()
+            .init_asset_loader::<WaveTimelineLoader>()
+            .init_resource::<WaveTimeline>()
+            .init_resource::<ShiftMode>()
+            .add_systems(OnEnter(GameState::Playing), (reset_timeline, setup_rush_warning))
+            .add_systems(Update, (
+                advance_timeline,
+                update_rush_warning,
+                end_workday,
+            ).run_if(in_state(GameState::Playing).and_then(in_state(crate::PausedState::Unpaused))))
+            .add_systems(Update, toggle_shift_mode.run_if(in_state(GameState::Menu).or_else(in_state(GameState::GameOver))))
+            .add_systems(OnExit(GameState::Playing), cleanup_rush_warning);
+    }
+}
+
+/// Every shift starts from the beginning of the file, so the changes of the file apply to the next shift.
+fn reset_timeline(
+    mut timeline: ResMut<WaveTimeline>,
+    shift_mode: Res<ShiftMode>,
+    config: Res<ConfigAssets>,
+    timelines: Res<Assets<WaveTimeline>>,
+) {
+    let Some(loaded) = timelines.get(shift_mode.timeline(&config)) else {
+        error!("timeline of {:?} is not loaded", *shift_mode);
+        return;
+    };
+
+    *timeline = loaded.clone();
+}
+
+fn advance_timeline(
+    time: Res<Time>,
+    mut timeline: ResMut<WaveTimeline>,
+) {
+    let previous = timeline.current;
+    timeline.advance(time.delta_seconds());
+
+    if timeline.current != previous {
+        if let Some(wave) = timeline.current() {
+            info!("wave: {}", wave.name);
+        }
+    }
+}
+
+fn setup_rush_warning(mut commands: Commands) {
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: 48.0,
+                    color: RUSH_WARNING_COLOR,
+                    ..default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        RushWarningText,
+    ));
+}
+
+fn update_rush_warning(
+    timeline: Res<WaveTimeline>,
+    mut query: Query<(&mut Text, &mut Visibility), With<RushWarningText>>,
+    localization: Res<Localization>,
+) {
+    let time_left = timeline.time_left();
+    let upcoming_rush = timeline.upcoming().filter(|wave| wave.rush && time_left <= RUSH_WARNING_TIME);
+
+    for (mut text, mut visibility) in &mut query {
+        match upcoming_rush {
+            Some(wave) => {
+                text.sections[0].value = localization.format("waves.rush_warning", &[
+                    ("wave", &localization.text(&wave.name)),
+                    ("seconds", &(time_left.ceil() as i32)),
+                ]);
+                *visibility = Visibility::Inherited;
+            },
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+fn cleanup_rush_warning(mut commands: Commands, query: Query<Entity, With<RushWarningText>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// The workday is over once the last parent went home.
+fn end_workday(
+    timeline: Res<WaveTimeline>,
+    parent_query: Query<(), With<Parent>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if timeline.is_over() && parent_query.is_empty() {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+fn toggle_shift_mode(
+    interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<ToggleShiftMode>)>,
+    mut texts: Query<&mut Text>,
+    mut shift_mode: ResMut<ShiftMode>,
+    localization: Res<Localization>,
+) {
+    for (interaction, children) in &interaction_query {
+        if *interaction != Interaction::Pressed { continue; }
+
+        *shift_mode = match *shift_mode {
+            ShiftMode::Endless => ShiftMode::Workday,
+            ShiftMode::Workday => ShiftMode::Endless,
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = localization.text(shift_mode.label_key());
+            }
+        }
+    }
+}
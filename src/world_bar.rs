@@ -0,0 +1,150 @@
+use bevy::{prelude::*, sprite::Anchor};
+
+/// Default look of a bar, the users only pick the size and the colors of the fill.
+const DEFAULT_BORDER: f32 = 2.0;
+const DEFAULT_BORDER_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+const BACKGROUND_COLOR: Color = Color::rgb(0.2, 0.1, 0.1);
+
+pub struct WorldBarPlugin;
+
+/// Progress bar drawn with sprites, spawned as a child of the entity it belongs to.
+/// Being a part of the world, it follows the entity around and scales together with the camera.
+#[derive(Component, Clone, Debug)]
+pub struct WorldBar {
+    /// 0 is empty, 1 is full.
+    pub progress: f32,
+    /// Outer size, including the border.
+    pub size: Vec2,
+    pub border: f32,
+    pub border_color: Color,
+    /// Colors of equally wide sections from the left, the bar empties from the right.
+    pub sections: Vec<Color>,
+}
+
+impl WorldBar {
+    pub fn new(size: Vec2, sections: Vec<Color>) -> Self {
+        WorldBar {
+            progress: 1.0,
+            size,
+            border: DEFAULT_BORDER,
+            border_color: DEFAULT_BORDER_COLOR,
+            sections,
+        }
+    }
+
+    pub fn with_border_color(mut self, color: Color) -> Self {
+        self.border_color = color;
+        self
+    }
+
+    pub fn with_progress(mut self, progress: f32) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    fn inner_size(&self) -> Vec2 {
+        (self.size - Vec2::splat(self.border * 2.0)).max(Vec2::ZERO)
+    }
+
+    /// How much of the section is filled, from 0 to 1.
+    fn section_fill(&self, index: usize) -> f32 {
+        let filled = self.progress.clamp(0.0, 1.0) * self.sections.len() as f32;
+        (filled - index as f32).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Bundle)]
+pub struct WorldBarBundle {
+    pub bar: WorldBar,
+    pub spatial: SpatialBundle,
+}
+
+impl WorldBarBundle {
+    /// Bar centered at the offset from its parent.
+    pub fn new(bar: WorldBar, offset: Vec3) -> Self {
+        WorldBarBundle {
+            bar,
+            spatial: SpatialBundle::from_transform(Transform::from_translation(offset)),
+        }
+    }
+}
+
+/// Sprite of a single section of a bar.
+#[derive(Component, Debug)]
+struct WorldBarSection(usize);
+
+impl Plugin for WorldBarPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                spawn_bar_sprites,
+                update_bar_sprites,
+            ));
+    }
+}
+
+fn spawn_bar_sprites(
+    mut commands: Commands,
+    query: Query<(Entity, &WorldBar), Added<WorldBar>>,
+) {
+    for (entity, bar) in &query {
+        let inner_size = bar.inner_size();
+        let section_width = inner_size.x / bar.sections.len().max(1) as f32;
+
+        commands.entity(entity).with_children(|children| {
+            children.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: bar.border_color,
+                    custom_size: Some(bar.size),
+                    ..default()
+                },
+                ..default()
+            });
+
+            children.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: BACKGROUND_COLOR,
+                    custom_size: Some(inner_size),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                ..default()
+            });
+
+            // sections are anchored on the left, so that a partly filled one shrinks towards it
+            for (index, &color) in bar.sections.iter().enumerate() {
+                let fill = bar.section_fill(index);
+                children.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color,
+                            custom_size: Some(Vec2::new(section_width, inner_size.y)),
+                            anchor: Anchor::CenterLeft,
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(-inner_size.x / 2.0 + index as f32 * section_width, 0.0, 0.02)
+                            .with_scale(Vec3::new(fill, 1.0, 1.0)),
+                        visibility: if fill > 0.0 { Visibility::Inherited } else { Visibility::Hidden },
+                        ..default()
+                    },
+                    WorldBarSection(index),
+                ));
+            }
+        });
+    }
+}
+
+fn update_bar_sprites(
+    bar_query: Query<(&WorldBar, &Children), Changed<WorldBar>>,
+    mut section_query: Query<(&WorldBarSection, &mut Transform, &mut Visibility)>,
+) {
+    for (bar, children) in &bar_query {
+        for child in children.iter() {
+            let Ok((section, mut transform, mut visibility)) = section_query.get_mut(*child) else { continue };
+
+            let fill = bar.section_fill(section.0);
+            transform.scale.x = fill;
+            *visibility = if fill > 0.0 { Visibility::Inherited } else { Visibility::Hidden };
+        }
+    }
+}